@@ -0,0 +1,123 @@
+//! GIF player: scales every frame of an animated GIF to the matrix, then
+//! sends the frames to the board at the pace of the GIF or saves them to a
+//! `.bin` file.
+
+use std::process::exit;
+use tp_led_matrix::gif;
+use tp_led_matrix::protocol;
+use tp_led_matrix::scale::{Dither, Filter, Fit, Options};
+use tp_led_matrix_host::output::Output;
+
+const USAGE: &str = "Usage: gif [OPTIONS] <FILE.gif>
+
+Options:
+  --filter NAME   nearest, bilinear or area (area)
+  --fit NAME      stretch, fit or fill (fit)
+  --dither NAME   none, floyd-steinberg or ordered (none)
+  --background C  colour around the picture with --fit fit (black)
+  --repeat N      times the animation is sent to the board, 0 for ever (1)
+  --bin FILE      write the frames to FILE instead of sending them
+  --serial DEV    serial port of the board (/dev/ttyACM0)";
+
+struct Args {
+    input: String,
+    options: Options,
+    repeat: u32,
+    bin: Option<String>,
+    serial: String,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        input: String::new(),
+        options: Options::default(),
+        repeat: 1,
+        bin: None,
+        serial: String::from("/dev/ttyACM0"),
+    };
+    let mut input = None;
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--filter" => {
+                args.options.filter = match value()?.as_str() {
+                    "nearest" => Filter::Nearest,
+                    "bilinear" => Filter::Bilinear,
+                    "area" => Filter::Area,
+                    other => return Err(format!("unknown filter {other}")),
+                }
+            }
+            "--fit" => {
+                args.options.fit = match value()?.as_str() {
+                    "stretch" => Fit::Stretch,
+                    "fit" => Fit::Fit,
+                    "fill" => Fit::Fill,
+                    other => return Err(format!("unknown fit {other}")),
+                }
+            }
+            "--dither" => {
+                args.options.dither = match value()?.as_str() {
+                    "none" => Dither::None,
+                    "floyd-steinberg" => Dither::FloydSteinberg,
+                    "ordered" => Dither::Ordered,
+                    other => return Err(format!("unknown dither {other}")),
+                }
+            }
+            "--background" => {
+                let color = value()?;
+                args.options.background =
+                    color.parse().map_err(|_| format!("bad colour {color}"))?;
+            }
+            "--repeat" => args.repeat = value()?.parse().map_err(|_| "bad --repeat")?,
+            "--bin" => args.bin = Some(value()?),
+            "--serial" => args.serial = value()?,
+            "-h" | "--help" => return Err(String::new()),
+            _ if input.is_none() && !arg.starts_with('-') => input = Some(arg),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+    args.input = input.ok_or("missing input file")?;
+    return Ok(args);
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let data = std::fs::read(&args.input).map_err(|e| format!("{}: {e}", args.input))?;
+    let frames =
+        gif::to_frames(&data, &args.options).map_err(|e| format!("{}: {e:?}", args.input))?;
+    if frames.is_empty() {
+        return Err(format!("{}: no frames", args.input));
+    }
+    if let Some(path) = args.bin {
+        // Timings are not part of the format, so the frames are only written once
+        return std::fs::write(&path, protocol::to_bin(&frames))
+            .map_err(|e| format!("{path}: {e}"));
+    }
+    let io_error = |e: std::io::Error| format!("{}: {e}", args.serial);
+    let mut output = Output::serial(&args.serial).map_err(io_error)?;
+    let mut played = 0;
+    while args.repeat == 0 || played < args.repeat {
+        for frame in &frames {
+            output.send(frame).map_err(io_error)?;
+        }
+        played += 1;
+    }
+    return output.finish().map_err(io_error);
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(error) => {
+            if !error.is_empty() {
+                eprintln!("gif: {error}");
+            }
+            eprintln!("{USAGE}");
+            exit(2);
+        }
+    };
+    if let Err(error) = run(args) {
+        eprintln!("gif: {error}");
+        exit(1);
+    }
+}
@@ -0,0 +1,94 @@
+//! Text rendering check: draws a UTF-8 string with one of the bundled fonts
+//! into a binary PPM as wide as the text, or compares the result with a
+//! reference PPM so that changes to the fonts or to the fallback can be
+//! spotted.
+
+use std::fs;
+use std::io::{self, Write};
+use std::process::exit;
+use tp_led_matrix::font::Font;
+use tp_led_matrix::fonts::{FONT_3X5, FONT_5X7, FONT_6X8};
+use tp_led_matrix::Color;
+use tp_led_matrix_host::text::Strip;
+
+const USAGE: &str = "Usage: render-text [OPTIONS] <TEXT>
+
+Options:
+  --font NAME     3x5, 5x7 or 6x8 (6x8)
+  --color COLOR   text colour, as understood by the firmware (white)
+  --out FILE      write the PPM to FILE instead of the standard output
+  --check FILE    compare with the PPM in FILE instead, failing if it differs";
+
+struct Args {
+    text: String,
+    font: &'static Font<'static>,
+    color: Color,
+    out: Option<String>,
+    check: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        text: String::new(),
+        font: &FONT_6X8,
+        color: Color::WHITE,
+        out: None,
+        check: None,
+    };
+    let mut text = None;
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--font" => {
+                args.font = match value()?.as_str() {
+                    "3x5" => &FONT_3X5,
+                    "5x7" => &FONT_5X7,
+                    "6x8" => &FONT_6X8,
+                    other => return Err(format!("unknown font {other}")),
+                }
+            }
+            "--color" => args.color = value()?.parse().map_err(|_| "bad --color")?,
+            "--out" => args.out = Some(value()?),
+            "--check" => args.check = Some(value()?),
+            "-h" | "--help" => return Err(String::new()),
+            _ if text.is_none() && !arg.starts_with("--") => text = Some(arg),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+    args.text = text.ok_or("missing text")?;
+    return Ok(args);
+}
+
+fn run(args: Args) -> io::Result<()> {
+    let ppm = Strip::text(&args.text, args.font, args.color).to_ppm();
+    if let Some(path) = args.check {
+        if fs::read(&path)? != ppm {
+            eprintln!("render-text: the rendering differs from {path}");
+            exit(1);
+        }
+        return Ok(());
+    }
+    match args.out {
+        Some(path) => fs::write(path, ppm)?,
+        None => io::stdout().write_all(&ppm)?,
+    }
+    return Ok(());
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(error) => {
+            if !error.is_empty() {
+                eprintln!("render-text: {error}");
+            }
+            eprintln!("{USAGE}");
+            exit(2);
+        }
+    };
+    if let Err(error) = run(args) {
+        eprintln!("render-text: {error}");
+        exit(1);
+    }
+}
@@ -0,0 +1,182 @@
+//! Spectrum display: turns a WAV file, or raw 16 bits PCM on the standard
+//! input, into bar graph frames sent to the board or saved to a `.bin` file.
+
+use std::io::{self, Read};
+use std::process::exit;
+use tp_led_matrix::protocol::Frame;
+use tp_led_matrix::spectrum::{Ramp, Visualiser, WINDOW};
+use tp_led_matrix::wav::{decode_sample, Encoding, Wav};
+use tp_led_matrix_host::output::{Output, MAX_FPS};
+
+const USAGE: &str = "Usage: spectrum [OPTIONS] <FILE.wav | ->
+
+Options:
+  --rate HZ       sample rate of the raw PCM read from the standard input (44100)
+  --channels N    channels of the raw PCM read from the standard input (1)
+  --fps N         frames per second, at most 19 on the serial port (15)
+  --ramp NAME     bar colours: vu, fire or rainbow (vu)
+  --no-peaks      do not show the peak-hold dots
+  --bin FILE      write the frames to FILE instead of sending them
+  --serial DEV    serial port of the board (/dev/ttyACM0)";
+
+struct Args {
+    input: String,
+    rate: u32,
+    channels: usize,
+    fps: u32,
+    ramp: Ramp,
+    peaks: bool,
+    bin: Option<String>,
+    serial: String,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        input: String::new(),
+        rate: 44100,
+        channels: 1,
+        fps: 15,
+        ramp: Ramp::Vu,
+        peaks: true,
+        bin: None,
+        serial: String::from("/dev/ttyACM0"),
+    };
+    let mut input = None;
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--rate" => args.rate = value()?.parse().map_err(|_| "bad --rate")?,
+            "--channels" => args.channels = value()?.parse().map_err(|_| "bad --channels")?,
+            "--fps" => args.fps = value()?.parse().map_err(|_| "bad --fps")?,
+            "--ramp" => {
+                args.ramp = match value()?.as_str() {
+                    "vu" => Ramp::Vu,
+                    "fire" => Ramp::Fire,
+                    "rainbow" => Ramp::Rainbow,
+                    other => return Err(format!("unknown ramp {other}")),
+                }
+            }
+            "--no-peaks" => args.peaks = false,
+            "--bin" => args.bin = Some(value()?),
+            "--serial" => args.serial = value()?,
+            "-h" | "--help" => return Err(String::new()),
+            _ if input.is_none() && (arg == "-" || !arg.starts_with('-')) => input = Some(arg),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+    args.input = input.ok_or("missing input file")?;
+    if args.rate == 0 || args.channels == 0 || args.fps == 0 {
+        return Err(String::from("--rate, --channels and --fps must not be 0"));
+    }
+    if args.bin.is_none() && args.fps > MAX_FPS {
+        return Err(format!(
+            "the serial port carries at most {MAX_FPS} frames per second"
+        ));
+    }
+    return Ok(args);
+}
+
+/// Cuts a stream of mono samples into frames at a fixed rate, each frame
+/// analysing the `WINDOW` samples which precede it
+struct Framer {
+    visualiser: Visualiser,
+    output: Output,
+    rate: u64,
+    fps: u64,
+    /// Last `WINDOW` samples, in a ring whose oldest sample is at `next`
+    history: [f32; WINDOW],
+    next: usize,
+    samples: u64,
+    frames: u64,
+}
+
+impl Framer {
+    fn push(&mut self, sample: f32) -> io::Result<()> {
+        self.history[self.next] = sample;
+        self.next = (self.next + 1) % WINDOW;
+        self.samples += 1;
+        // Frame n covers the audio up to sample (n + 1) * rate / fps, so that
+        // rounding never accumulates
+        if self.samples * self.fps >= (self.frames + 1) * self.rate {
+            let delay_ms = (self.frames + 1) * 1000 / self.fps - self.frames * 1000 / self.fps;
+            let mut window = self.history;
+            window.rotate_left(self.next);
+            let frame = Frame {
+                image: self.visualiser.process(&window),
+                delay_ms: delay_ms as u32,
+            };
+            self.output.send(&frame)?;
+            self.frames += 1;
+        }
+        return Ok(());
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let file;
+    let wav = if args.input == "-" {
+        None
+    } else {
+        file = std::fs::read(&args.input).map_err(|e| format!("{}: {e}", args.input))?;
+        Some(Wav::parse(&file).map_err(|e| format!("{}: {e:?}", args.input))?)
+    };
+    let rate = wav.as_ref().map_or(args.rate, |wav| wav.sample_rate);
+
+    let mut visualiser = Visualiser::new(rate);
+    visualiser.ramp = args.ramp;
+    if !args.peaks {
+        visualiser.peak_color = None;
+    }
+    let output = Output::from_args(args.bin.as_deref(), &args.serial)
+        .map_err(|e| format!("{}: {e}", args.bin.as_deref().unwrap_or(&args.serial)))?;
+    let mut framer = Framer {
+        visualiser,
+        output,
+        rate: rate as u64,
+        fps: args.fps as u64,
+        history: [0.0; WINDOW],
+        next: 0,
+        samples: 0,
+        frames: 0,
+    };
+
+    let io_error = |e: io::Error| e.to_string();
+    match wav {
+        Some(wav) => {
+            for sample in wav.mono() {
+                framer.push(sample).map_err(io_error)?;
+            }
+        }
+        None => {
+            // Raw interleaved 16 bits little-endian samples, processed as they come
+            let mut stdin = io::stdin().lock();
+            let mut frame = vec![0u8; 2 * args.channels];
+            while stdin.read_exact(&mut frame).is_ok() {
+                let sum: f32 = frame
+                    .chunks_exact(2)
+                    .map(|sample| decode_sample(sample, Encoding::Pcm))
+                    .sum();
+                framer.push(sum / args.channels as f32).map_err(io_error)?;
+            }
+        }
+    }
+    return framer.output.finish().map_err(io_error);
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(error) => {
+            if !error.is_empty() {
+                eprintln!("spectrum: {error}");
+            }
+            eprintln!("{USAGE}");
+            exit(2);
+        }
+    };
+    if let Err(error) = run(args) {
+        eprintln!("spectrum: {error}");
+        exit(1);
+    }
+}
@@ -0,0 +1,234 @@
+//! Virtual board: creates a pseudo-terminal behaving like the serial port of
+//! the board, and runs the firmware logic behind it. Every decoded event is
+//! logged, and what the panel shows can be saved to a PPM file or drawn in
+//! the terminal, so that host tools can be tested without hardware. A file
+//! can stand for the flash page where the board saves its playlist.
+
+use std::io::{self, Read, Write};
+use std::process::exit;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Instant;
+use tp_led_matrix::playlist::Playlist;
+use tp_led_matrix::ppm::{self, Format, BINARY_PPM_LEN};
+use tp_led_matrix::protocol::Event;
+use tp_led_matrix::Image;
+use tp_led_matrix_host::board::Board;
+use tp_led_matrix_host::pty::Pty;
+
+const USAGE: &str = "Usage: virtual-board [OPTIONS]
+
+Options:
+  --link PATH     also make PATH a symbolic link to the serial device
+  --state FILE    write what the panel shows to FILE (binary PPM) after every change
+  --show          draw the panel in the terminal after every received image
+  --playlist FILE load the playlist from FILE, and save it there when commands change it
+  --quiet         do not log the screensaver";
+
+#[derive(Default)]
+struct Args {
+    link: Option<String>,
+    state: Option<String>,
+    show: bool,
+    quiet: bool,
+    playlist: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--link" => args.link = Some(value()?),
+            "--state" => args.state = Some(value()?),
+            "--show" => args.show = true,
+            "--quiet" => args.quiet = true,
+            "--playlist" => args.playlist = Some(value()?),
+            "-h" | "--help" => return Err(String::new()),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+    return Ok(args);
+}
+
+struct Logger {
+    start: Instant,
+    args: Args,
+    /// Bytes dropped since the last logged event
+    ignored: usize,
+}
+
+impl Logger {
+    fn log(&self, message: &str) {
+        let elapsed = self.start.elapsed();
+        println!(
+            "{:5}.{:03} {message}",
+            elapsed.as_secs(),
+            elapsed.subsec_millis()
+        );
+    }
+
+    fn flush_ignored(&mut self) {
+        if self.ignored != 0 {
+            self.log(&format!(
+                "ignored {} bytes before a start byte",
+                self.ignored
+            ));
+            self.ignored = 0;
+        }
+    }
+
+    fn event(&mut self, event: Event, board: &Board) {
+        match event {
+            Event::Ignored => self.ignored += 1,
+            Event::Restarted { received } => {
+                self.flush_ignored();
+                self.log(&format!("start byte after {received} bytes, image dropped"));
+            }
+            Event::Complete => {
+                self.flush_ignored();
+                let mean = mean(&board.panel);
+                self.log(&format!(
+                    "image {} received, mean colour #{:02x}{:02x}{:02x}",
+                    board.changes, mean[0], mean[1], mean[2]
+                ));
+            }
+            // Logged with its outcome once it has been carried out
+            Event::Command => self.flush_ignored(),
+        }
+    }
+
+    fn panel(&self, panel: &Image, received: bool) -> io::Result<()> {
+        if let Some(path) = &self.args.state {
+            let mut buffer = [0; BINARY_PPM_LEN];
+            let len = ppm::encode(panel, Format::Binary, &mut buffer).unwrap();
+            // Readers never see a half-written file
+            let tmp = format!("{path}.tmp");
+            std::fs::write(&tmp, &buffer[..len])?;
+            std::fs::rename(&tmp, path)?;
+        }
+        if self.args.show && received {
+            let mut out = io::stdout().lock();
+            for row in 0..8 {
+                for pixel in panel.row(row) {
+                    write!(out, "\x1b[48;2;{};{};{}m  ", pixel.r, pixel.g, pixel.b)?;
+                }
+                writeln!(out, "\x1b[0m")?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+fn mean(image: &Image) -> [u8; 3] {
+    let mut sum = [0u32; 3];
+    for (i, &byte) in image.as_ref().iter().enumerate() {
+        sum[i % 3] += byte as u32;
+    }
+    return [
+        (sum[0] / 64) as u8,
+        (sum[1] / 64) as u8,
+        (sum[2] / 64) as u8,
+    ];
+}
+
+fn run(args: Args) -> io::Result<()> {
+    let pty = Pty::open()?;
+    if let Some(link) = &args.link {
+        // A link left by a previous run is replaced
+        if std::fs::symlink_metadata(link).is_ok_and(|m| m.file_type().is_symlink()) {
+            std::fs::remove_file(link)?;
+        }
+        std::os::unix::fs::symlink(&pty.path, link)?;
+    }
+    println!(
+        "virtual board listening on {}",
+        args.link.as_ref().unwrap_or(&pty.path)
+    );
+
+    // The pseudo-terminal is read in its own thread so that the screensaver
+    // keeps running while nothing is received
+    let (sender, receiver) = mpsc::channel();
+    let mut master = pty.master.try_clone()?;
+    std::thread::spawn(move || {
+        let mut buffer = [0; 256];
+        while let Ok(len) = master.read(&mut buffer) {
+            if len == 0 || sender.send(buffer[..len].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let playlist = match &args.playlist {
+        Some(path) if std::path::Path::new(path).exists() => std::fs::read_to_string(path)?
+            .parse()
+            .map_err(|error| io::Error::other(format!("bad playlist in {path}: {error:?}")))?,
+        _ => Playlist::new(),
+    };
+    let start = Instant::now();
+    let mut board = Board::new(start, playlist);
+    let mut logger = Logger {
+        start,
+        args,
+        ignored: 0,
+    };
+    let mut screensaver_running = false;
+    loop {
+        let timeout = board.next_step().saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(timeout) {
+            Ok(bytes) => {
+                for byte in bytes {
+                    if let Some(event) = board.receive(byte) {
+                        logger.event(event, &board);
+                        if event == Event::Command {
+                            let line = board.command().unwrap_or("(not UTF-8)").to_string();
+                            match board.run_command(Instant::now()) {
+                                Ok(changed) => {
+                                    logger.log(&format!("command {line:?}"));
+                                    if let (true, Some(path)) = (changed, &logger.args.playlist) {
+                                        std::fs::write(path, board.playlist().to_string())?;
+                                    }
+                                }
+                                Err(error) => {
+                                    logger.log(&format!("command {line:?} failed: {error:?}"))
+                                }
+                            }
+                        }
+                        if event == Event::Complete {
+                            screensaver_running = false;
+                            logger.panel(&board.panel, true)?;
+                        }
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if board.step(Instant::now()) {
+                    if !screensaver_running && !logger.args.quiet {
+                        logger.log("screensaver running");
+                    }
+                    screensaver_running = true;
+                    logger.panel(&board.panel, false)?;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    return Ok(());
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(error) => {
+            if !error.is_empty() {
+                eprintln!("virtual-board: {error}");
+            }
+            eprintln!("{USAGE}");
+            exit(2);
+        }
+    };
+    if let Err(error) = run(args) {
+        eprintln!("virtual-board: {error}");
+        exit(1);
+    }
+}
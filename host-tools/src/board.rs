@@ -0,0 +1,109 @@
+use std::time::{Duration, Instant};
+use tp_led_matrix::animations::ANIMATIONS;
+use tp_led_matrix::correction::Profile;
+use tp_led_matrix::playlist::{self, Command, Playlist, Scheduler};
+use tp_led_matrix::protocol::{Event, Receiver};
+use tp_led_matrix::screensaver::{Screensaver, PAUSE_MS, STEP_MS};
+use tp_led_matrix::Image;
+
+/// Seed of the shuffled playlists and of the random effects, as on the board
+const PLAYLIST_SEED: u32 = 202;
+
+/// The firmware logic without the hardware: the serial decoder, the image
+/// hand-over to the display, the commands, the playlist and the screensaver,
+/// driven by the host clock
+pub struct Board {
+    receiver: Receiver,
+    rx_image: Image,
+    screensaver: Screensaver,
+    scheduler: Scheduler<'static>,
+    start: Instant,
+    next_step: Instant,
+    /// What the matrix currently shows
+    pub panel: Image,
+    /// Number of images received so far
+    pub changes: u32,
+    /// Orientation and colour correction set by `profile` commands, which
+    /// the board applies when sending the panel to the matrix
+    pub profile: Profile,
+}
+
+impl Board {
+    /// Board started at `now` with `playlist`, as saved before a reset
+    pub fn new(now: Instant, playlist: Playlist) -> Self {
+        return Board {
+            receiver: Receiver::new(),
+            rx_image: Image::default(),
+            screensaver: Screensaver::new(),
+            scheduler: Scheduler::new(playlist, &ANIMATIONS, PLAYLIST_SEED),
+            start: now,
+            next_step: now,
+            panel: Image::default(),
+            changes: 0,
+            profile: Profile::NEUTRAL,
+        };
+    }
+
+    /// Handle a byte received on the serial line. A complete image is shown
+    /// right away, instead of at the end of the current display refresh.
+    pub fn receive(&mut self, byte: u8) -> Option<Event> {
+        let event = self.receiver.receive(byte, &mut self.rx_image);
+        if event == Some(Event::Complete) {
+            core::mem::swap(&mut self.panel, &mut self.rx_image);
+            self.changes = self.changes.saturating_add(1);
+        }
+        return event;
+    }
+
+    /// Time of the monotonic timer of the board at `now`
+    fn millis(&self, now: Instant) -> tp_led_matrix::Instant {
+        let ms = now.duration_since(self.start).as_millis() as u64;
+        return tp_led_matrix::Instant::from_ticks(ms);
+    }
+
+    /// Text of the command just received, if it is valid UTF-8
+    pub fn command(&self) -> Option<&str> {
+        return self.receiver.command();
+    }
+
+    /// Carry out the command just received, and tell whether the playlist
+    /// changed and should be saved
+    pub fn run_command(&mut self, now: Instant) -> Result<bool, playlist::Error> {
+        let line = self.receiver.command().unwrap_or_default();
+        let command: Command = line.parse()?;
+        if let Command::Profile(setting) = &command {
+            self.profile.apply(setting);
+        }
+        return self.scheduler.apply(&command, self.millis(now));
+    }
+
+    pub fn playlist(&self) -> &Playlist {
+        return self.scheduler.playlist();
+    }
+
+    /// When the screensaver task runs next
+    pub fn next_step(&self) -> Instant {
+        return self.next_step;
+    }
+
+    /// Run the screensaver task if it is due, and tell whether it changed
+    /// the panel
+    pub fn step(&mut self, now: Instant) -> bool {
+        if now < self.next_step {
+            return false;
+        }
+        if self.screensaver.is_idle(self.changes) {
+            // The playlist if there is one, the screensaver otherwise.
+            // Transitions are not emulated.
+            let t = self.millis(now);
+            if self.scheduler.render(t, &mut self.panel).is_none() {
+                self.panel = self.screensaver.render();
+            }
+            self.screensaver.advance();
+            self.next_step += Duration::from_millis(STEP_MS as u64);
+            return true;
+        }
+        self.next_step += Duration::from_millis(PAUSE_MS as u64);
+        return false;
+    }
+}
@@ -0,0 +1,4 @@
+pub mod board;
+pub mod output;
+pub mod pty;
+pub mod text;
@@ -0,0 +1,70 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::time::{Duration, Instant};
+use tp_led_matrix::protocol::{encode_frame, Frame, FRAME_LEN};
+
+/// Speed of the serial port set by `bin/stty.sh`
+pub const BAUD_RATE: u32 = 38400;
+
+/// Frames per second the serial port carries, a byte taking 10 bits with its
+/// start and stop bits
+pub const MAX_FPS: u32 = BAUD_RATE / 10 / FRAME_LEN as u32;
+
+/// Where the frames produced by a tool go
+pub enum Output {
+    /// A `.bin` file, written as fast as possible
+    Bin(BufWriter<File>),
+    /// The serial port of the board, configured beforehand with `bin/stty.sh`.
+    /// Every frame is sent when it is due.
+    Serial {
+        port: File,
+        start: Instant,
+        due: Duration,
+    },
+}
+
+impl Output {
+    pub fn bin(path: &str) -> io::Result<Self> {
+        return Ok(Output::Bin(BufWriter::new(File::create(path)?)));
+    }
+
+    pub fn serial(path: &str) -> io::Result<Self> {
+        let port = OpenOptions::new().write(true).open(path)?;
+        return Ok(Output::Serial {
+            port,
+            start: Instant::now(),
+            due: Duration::ZERO,
+        });
+    }
+
+    /// Open `--bin FILE` if given, the serial port `--serial DEV` otherwise
+    pub fn from_args(bin: Option<&str>, serial: &str) -> io::Result<Self> {
+        return match bin {
+            Some(path) => Output::bin(path),
+            None => Output::serial(serial),
+        };
+    }
+
+    pub fn send(&mut self, frame: &Frame) -> io::Result<()> {
+        match self {
+            Output::Bin(file) => file.write_all(&encode_frame(&frame.image))?,
+            Output::Serial { port, start, due } => {
+                let now = start.elapsed();
+                if *due > now {
+                    std::thread::sleep(*due - now);
+                }
+                port.write_all(&encode_frame(&frame.image))?;
+                port.flush()?;
+                *due += Duration::from_millis(frame.delay_ms as u64);
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn finish(self) -> io::Result<()> {
+        match self {
+            Output::Bin(mut file) => return file.flush(),
+            Output::Serial { .. } => return Ok(()),
+        }
+    }
+}
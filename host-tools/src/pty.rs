@@ -0,0 +1,56 @@
+use std::ffi::CStr;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::os::unix::fs::OpenOptionsExt;
+
+/// A pseudo-terminal pair: programs open `path` as they would open the
+/// serial port of the board, and what they write comes out of `master`.
+pub struct Pty {
+    pub master: File,
+    pub path: String,
+    /// Kept open so that reading the master does not fail each time the
+    /// last program using the slave closes it
+    _slave: File,
+}
+
+fn check(result: libc::c_int) -> io::Result<libc::c_int> {
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    return Ok(result);
+}
+
+impl Pty {
+    pub fn open() -> io::Result<Self> {
+        // SAFETY: plain libc calls on a file descriptor owned by this function
+        unsafe {
+            let fd = check(libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY))?;
+            let master = File::from_raw_fd(fd);
+            check(libc::grantpt(fd))?;
+            check(libc::unlockpt(fd))?;
+            let mut name = [0 as libc::c_char; 128];
+            if libc::ptsname_r(fd, name.as_mut_ptr(), name.len()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let path = CStr::from_ptr(name.as_ptr()).to_string_lossy().into_owned();
+
+            let slave = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .custom_flags(libc::O_NOCTTY)
+                .open(&path)?;
+            // Like `bin/stty.sh` does on the real port: no echo, no line editing
+            let mut termios = std::mem::zeroed::<libc::termios>();
+            check(libc::tcgetattr(slave.as_raw_fd(), &mut termios))?;
+            libc::cfmakeraw(&mut termios);
+            check(libc::tcsetattr(slave.as_raw_fd(), libc::TCSANOW, &termios))?;
+
+            return Ok(Pty {
+                master,
+                path,
+                _slave: slave,
+            });
+        }
+    }
+}
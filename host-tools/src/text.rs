@@ -0,0 +1,68 @@
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::{Baseline, Text};
+use tp_led_matrix::font::{Font, FontStyle};
+use tp_led_matrix::Color;
+
+/// Image of any size, drawn into like `Image`
+pub struct Strip {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<Color>,
+}
+
+impl Strip {
+    pub fn new(width: u32, height: u32) -> Self {
+        let pixels = vec![Color::BLACK; (width * height) as usize];
+        return Strip {
+            width,
+            height,
+            pixels,
+        };
+    }
+
+    /// `text` drawn with `font` on a black strip as wide as the text
+    pub fn text(text: &str, font: &Font, color: Color) -> Self {
+        let style = FontStyle::new(font, color.into());
+        let text = Text::with_baseline(text, Point::zero(), style, Baseline::Top);
+        let size = text.bounding_box().size;
+        let mut strip = Strip::new(size.width, size.height);
+        let Ok(_) = text.draw(&mut strip);
+        return strip;
+    }
+
+    /// Binary PPM of the strip
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in &self.pixels {
+            ppm.extend_from_slice(&[pixel.r, pixel.g, pixel.b]);
+        }
+        return ppm;
+    }
+}
+
+impl OriginDimensions for Strip {
+    fn size(&self) -> Size {
+        return Size::new(self.width, self.height);
+    }
+}
+
+impl DrawTarget for Strip {
+    type Color = Rgb888;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let (Ok(x), Ok(y)) = (u32::try_from(point.x), u32::try_from(point.y)) else {
+                continue;
+            };
+            if x < self.width && y < self.height {
+                self.pixels[(y * self.width + x) as usize] = color.into();
+            }
+        }
+        return Ok(());
+    }
+}
@@ -0,0 +1,113 @@
+use tp_led_matrix::automaton::{Automaton, Error, Rule, LINGER_GENERATIONS};
+
+/// Live cells, as (x, y), sorted by row then column
+fn live_cells(automaton: &Automaton) -> Vec<(usize, usize)> {
+    let mut cells = Vec::new();
+    for y in 0..8 {
+        for x in 0..8 {
+            if automaton.is_alive(x, y) {
+                cells.push((x, y));
+            }
+        }
+    }
+    return cells;
+}
+
+fn with_cells(cells: &[(usize, usize)]) -> Automaton {
+    let mut automaton = Automaton::new(Rule::LIFE, 1);
+    for &(x, y) in cells {
+        automaton.set_alive(x, y, true);
+    }
+    return automaton;
+}
+
+#[test]
+fn rule_round_trip() {
+    for (text, rule) in [
+        ("B3/S23", Rule::LIFE),
+        ("B36/S23", Rule::HIGHLIFE),
+        ("B2/S", Rule::SEEDS),
+        ("B3678/S34678", Rule::DAY_AND_NIGHT),
+        ("B/S012345678", Rule::new(0, 0x1ff)),
+    ] {
+        assert_eq!(text.parse(), Ok(rule));
+        assert_eq!(rule.to_string(), text);
+    }
+    assert_eq!("b3/s23".parse(), Ok(Rule::LIFE));
+    assert_eq!(" B3/S32 ".parse(), Ok(Rule::LIFE));
+}
+
+#[test]
+fn rule_errors() {
+    for text in ["B9/S23", "B3/S239", "B3x/S23", "B-1/S23"] {
+        assert_eq!(text.parse::<Rule>(), Err(Error::BadCount), "{text}");
+    }
+    for text in ["", "B3S23", "3/23", "B3/23", "S23/B3"] {
+        assert_eq!(text.parse::<Rule>(), Err(Error::BadFormat), "{text}");
+    }
+}
+
+#[test]
+fn blinker() {
+    let horizontal = [(2, 3), (3, 3), (4, 3)];
+    let vertical = [(3, 2), (3, 3), (3, 4)];
+    let mut automaton = with_cells(&horizontal);
+    for _ in 0..4 {
+        assert!(!automaton.step());
+        assert_eq!(live_cells(&automaton), vertical);
+        assert_eq!(automaton.age(3, 3), automaton.generation() as u8 + 1);
+        assert!(!automaton.step());
+        assert_eq!(live_cells(&automaton), horizontal);
+    }
+}
+
+#[test]
+fn glider() {
+    let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+    let mut automaton = with_cells(&glider);
+    for _ in 0..4 {
+        assert!(!automaton.step());
+    }
+    let mut expected: Vec<_> = glider.iter().map(|&(x, y)| (x + 1, y + 1)).collect();
+    expected.sort_by_key(|&(x, y)| (y, x));
+    assert_eq!(live_cells(&automaton), expected);
+    // Back where it started once it has gone round the torus
+    for _ in 4..32 {
+        assert!(!automaton.step());
+    }
+    let mut expected = glider.to_vec();
+    expected.sort_by_key(|&(x, y)| (y, x));
+    assert_eq!(live_cells(&automaton), expected);
+}
+
+/// The same seed always gives the same boards, reseedings included
+#[test]
+fn seed_is_reproducible() {
+    let mut automaton: Automaton = Automaton::new(Rule::LIFE, 202);
+    let mut reseeds = 0;
+    for _ in 0..200 {
+        reseeds += automaton.step() as u32;
+    }
+    // FNV-1a of the ages
+    let mut hash = 0x811c_9dc5_u32;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash = (hash ^ automaton.age(x, y) as u32).wrapping_mul(0x0100_0193);
+        }
+    }
+    assert_eq!((reseeds, hash), (5, 0xe596_cdb0));
+}
+
+#[test]
+fn still_life_lingers_then_reseeds() {
+    let block = [(3, 3), (4, 3), (3, 4), (4, 4)];
+    let mut automaton = with_cells(&block);
+    // The repetition is noticed at the second generation
+    for _ in 0..2 + LINGER_GENERATIONS {
+        assert!(!automaton.step());
+        assert_eq!(live_cells(&automaton), block);
+    }
+    assert!(automaton.step());
+    assert_eq!(automaton.generation(), 0);
+    assert_ne!(live_cells(&automaton), block);
+}
@@ -0,0 +1,120 @@
+use std::time::{Duration, Instant};
+use tp_led_matrix::correction::Profile;
+use tp_led_matrix::playlist::{self, Playlist};
+use tp_led_matrix::protocol::{self, Event, FRAME_LEN};
+use tp_led_matrix::transform::Rotation;
+use tp_led_matrix::{Color, Image};
+use tp_led_matrix_host::board::Board;
+
+fn sample() -> Image {
+    let mut image = Image::default();
+    for ((x, y), pixel) in image.pixels_mut() {
+        *pixel = Color {
+            r: (x * 36) as u8,
+            g: (y * 36) as u8,
+            b: 255,
+        };
+    }
+    return image;
+}
+
+/// Events of `board` while receiving `bytes`
+fn feed(board: &mut Board, bytes: &[u8]) -> Vec<Option<Event>> {
+    return bytes.iter().map(|&byte| board.receive(byte)).collect();
+}
+
+fn send_command(board: &mut Board, command: &str) -> Vec<Option<Event>> {
+    let mut frame = vec![0; command.len() + 4];
+    let len = protocol::write_command(command, &mut frame);
+    return feed(board, &frame[..len]);
+}
+
+#[test]
+fn frame_is_shown() {
+    let mut board = Board::new(Instant::now(), Playlist::new());
+    let events = feed(&mut board, &protocol::encode_frame(&sample()));
+    assert!(events[..FRAME_LEN - 1].iter().all(Option::is_none));
+    assert_eq!(events[FRAME_LEN - 1], Some(Event::Complete));
+    assert_eq!(board.changes, 1);
+    // 255 cannot be sent, as it is the start byte
+    let expected = sample().map(|color| Color {
+        b: color.b.min(254),
+        ..color
+    });
+    assert_eq!(board.panel.as_bytes(), expected.as_bytes());
+    // Nothing more until the next start byte
+    assert_eq!(board.receive(0), Some(Event::Ignored));
+}
+
+#[test]
+fn interrupted_frame_restarts() {
+    let mut board = Board::new(Instant::now(), Playlist::new());
+    let frame = protocol::encode_frame(&sample());
+    feed(&mut board, &frame[..10]);
+    assert_eq!(
+        board.receive(protocol::START),
+        Some(Event::Restarted { received: 9 })
+    );
+    let events = feed(&mut board, &frame[1..]);
+    assert_eq!(events.last(), Some(&Some(Event::Complete)));
+    assert_eq!(board.changes, 1);
+}
+
+#[test]
+fn command_edits_playlist() {
+    let now = Instant::now();
+    let mut board = Board::new(now, Playlist::new());
+    let events = send_command(&mut board, "add 10s text Hello");
+    assert!(events[..events.len() - 1].iter().all(Option::is_none));
+    assert_eq!(events.last(), Some(&Some(Event::Command)));
+    assert_eq!(board.command(), Some("add 10s text Hello"));
+    assert_eq!(board.run_command(now), Ok(true));
+    assert_eq!(board.playlist().entries().len(), 1);
+    assert_eq!(board.playlist().to_string(), "add 10s cut text Hello\n");
+    // A command does not count as a received image
+    assert_eq!(board.changes, 0);
+
+    send_command(&mut board, "remove 5");
+    assert_eq!(board.run_command(now), Err(playlist::Error::BadIndex));
+    send_command(&mut board, "frobnicate");
+    assert_eq!(board.run_command(now), Err(playlist::Error::UnknownCommand));
+    assert_eq!(board.playlist().entries().len(), 1);
+}
+
+#[test]
+fn command_sets_profile() {
+    let now = Instant::now();
+    let mut board = Board::new(now, Playlist::new());
+    send_command(&mut board, "profile rotate 90");
+    assert_eq!(board.run_command(now), Ok(false));
+    send_command(&mut board, "profile temperature 3000");
+    assert_eq!(board.run_command(now), Ok(false));
+    assert_eq!(board.profile.orientation.rotation, Rotation::Deg90);
+    assert_eq!(board.profile.correction.temperature, 3000);
+    assert!(board.playlist().is_empty());
+
+    send_command(&mut board, "profile rotate 45");
+    assert_eq!(board.run_command(now), Err(playlist::Error::BadArguments));
+    send_command(&mut board, "profile reset");
+    assert_eq!(board.run_command(now), Ok(false));
+    assert_eq!(board.profile, Profile::NEUTRAL);
+}
+
+#[test]
+fn screensaver_comes_back_after_a_pause() {
+    let start = Instant::now();
+    let mut board = Board::new(start, Playlist::new());
+    assert!(board.step(start));
+    feed(&mut board, &protocol::encode_frame(&sample()));
+    let received = board.panel.clone();
+
+    // The received image stays for a while
+    let now = board.next_step();
+    assert!(!board.step(now));
+    assert_eq!(board.panel.as_bytes(), received.as_bytes());
+    assert_eq!(board.next_step() - now, Duration::from_millis(1000));
+
+    // Then the screensaver takes over again
+    assert!(board.step(board.next_step()));
+    assert_ne!(board.panel.as_bytes(), received.as_bytes());
+}
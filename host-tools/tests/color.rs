@@ -0,0 +1,79 @@
+use tp_led_matrix::Color;
+
+fn grey(v: u8) -> Color {
+    return Color { r: v, g: v, b: v };
+}
+
+/// Whether `value` is the nearest integer to the float reference, which is
+/// within one LSB of it
+fn nearest(value: u8, reference: f64) -> bool {
+    return (value as f64 - reference).abs() <= 0.5;
+}
+
+#[test]
+fn scale_and_modulate() {
+    for a in 0..=255 {
+        for b in 0..=255 {
+            let reference = a as f64 * b as f64 / 255.0;
+            let scaled = grey(a).scale(b);
+            assert!(nearest(scaled.r, reference), "{a} scaled by {b}");
+            assert_eq!(scaled, grey(a) * grey(b));
+            assert_eq!(grey(a) * grey(b), grey(b) * grey(a));
+        }
+    }
+    assert_eq!(Color::ORANGE.scale(255), Color::ORANGE);
+    assert_eq!(Color::ORANGE * Color::WHITE, Color::ORANGE);
+    assert_eq!(Color::ORANGE.scale(0), Color::BLACK);
+}
+
+#[test]
+fn lerp() {
+    for a in 0..=255 {
+        for b in 0..=255 {
+            for t in 0..=255 {
+                let reference = a as f64 + (b as f64 - a as f64) * t as f64 / 255.0;
+                let mixed = Color::lerp(grey(a), grey(b), t);
+                assert!(nearest(mixed.r, reference), "{a} to {b} at {t}");
+            }
+        }
+    }
+    assert_eq!(Color::lerp(Color::RED, Color::BLUE, 0), Color::RED);
+    assert_eq!(Color::lerp(Color::RED, Color::BLUE, 255), Color::BLUE);
+    assert_eq!(Color::lerp(Color::BLACK, Color::WHITE, 128), grey(128));
+}
+
+#[test]
+fn saturating_arithmetic() {
+    for a in 0..=255u8 {
+        for b in 0..=255u8 {
+            assert_eq!((grey(a) + grey(b)).r, a.saturating_add(b));
+            assert_eq!((grey(a) - grey(b)).r, a.saturating_sub(b));
+        }
+    }
+    let mut color = Color::ORANGE;
+    color += Color::BLUE;
+    assert_eq!(
+        color,
+        Color {
+            r: 255,
+            g: 165,
+            b: 255
+        }
+    );
+    color -= Color::WHITE;
+    assert_eq!(color, Color::BLACK);
+}
+
+#[test]
+fn average() {
+    assert_eq!(Color::average(&[]), Color::BLACK);
+    assert_eq!(Color::average(&[Color::PINK]), Color::PINK);
+    assert_eq!(
+        Color::average(&[Color::RED, Color::GREEN, Color::BLUE]),
+        grey(85)
+    );
+    // 127.5 is rounded up
+    assert_eq!(Color::average(&[Color::BLACK, Color::WHITE]), grey(128));
+    let colors: Vec<Color> = (0..=255).map(grey).collect();
+    assert!(nearest(Color::average(&colors).r, 127.5));
+}
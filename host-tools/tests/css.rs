@@ -0,0 +1,120 @@
+use tp_led_matrix::css::{Error, NAMES};
+use tp_led_matrix::Color;
+
+const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    return Color { r, g, b };
+}
+
+fn parse(s: &str) -> Result<Color, Error> {
+    return s.parse();
+}
+
+#[test]
+fn hex() {
+    assert_eq!(parse("#ff8000"), Ok(rgb(255, 128, 0)));
+    assert_eq!(parse("#0a0B0c"), Ok(rgb(10, 11, 12)));
+    // A single digit is repeated
+    assert_eq!(parse("#f80"), Ok(rgb(255, 136, 0)));
+    assert_eq!(parse("#ABC"), Ok(rgb(170, 187, 204)));
+    for s in [
+        "#", "#ff", "#ff80", "#ff800", "#ff80000", "#ggg", "#+12", "#12345z",
+    ] {
+        assert_eq!(parse(s), Err(Error::BadHex), "{s}");
+    }
+}
+
+#[test]
+fn functions() {
+    assert_eq!(parse("rgb(255, 128, 0)"), Ok(rgb(255, 128, 0)));
+    assert_eq!(parse("rgb(1 2 3)"), Ok(rgb(1, 2, 3)));
+    assert_eq!(parse("rgb(100%, 50%, 0%)"), Ok(rgb(255, 128, 0)));
+    assert_eq!(parse("hsv(0, 100, 100)"), Ok(Color::RED));
+    assert_eq!(parse("hsv(120deg, 100%, 100%)"), Ok(rgb(0, 255, 0)));
+    assert_eq!(parse("hsv(240, 100%, 50%)"), Ok(rgb(0, 0, 128)));
+    assert_eq!(parse("hsv(600, 0%, 100%)"), Ok(Color::WHITE));
+    assert_eq!(parse("hsl(0, 100%, 50%)"), Ok(Color::RED));
+    assert_eq!(parse("hsl(240, 100%, 50%)"), Ok(Color::BLUE));
+    assert_eq!(parse("hsl(120, 100%, 25%)"), Ok(rgb(0, 128, 0)));
+    assert_eq!(parse("hsl(300, 100%, 0%)"), Ok(Color::BLACK));
+}
+
+#[test]
+fn names() {
+    for (name, color) in NAMES {
+        assert_eq!(parse(name), Ok(color), "{name}");
+    }
+    assert!(NAMES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    // The constants of `Color` sharing a name with a CSS colour
+    for (name, color) in [
+        ("black", Color::BLACK),
+        ("white", Color::WHITE),
+        ("red", Color::RED),
+        ("blue", Color::BLUE),
+        ("yellow", Color::YELLOW),
+        ("cyan", Color::CYAN),
+        ("magenta", Color::MAGENTA),
+        ("orange", Color::ORANGE),
+        ("purple", Color::PURPLE),
+        ("pink", Color::PINK),
+    ] {
+        assert_eq!(parse(name), Ok(color), "{name}");
+    }
+    assert_eq!(parse("reed"), Err(Error::UnknownName));
+    assert_eq!(parse("red blue"), Err(Error::UnknownName));
+}
+
+#[test]
+fn case_and_whitespace() {
+    assert_eq!(parse("  DarkOrange\n"), Ok(rgb(255, 140, 0)));
+    assert_eq!(parse("\t#FF8000 "), Ok(rgb(255, 128, 0)));
+    assert_eq!(parse(" RGB ( 1 ,2,  3 ) "), Ok(rgb(1, 2, 3)));
+    assert_eq!(parse("HSL(0,100%,50%)"), Ok(Color::RED));
+    assert_eq!(parse(""), Err(Error::Empty));
+    assert_eq!(parse(" \t"), Err(Error::Empty));
+}
+
+#[test]
+fn out_of_range() {
+    for s in [
+        "rgb(256, 0, 0)",
+        "rgb(0, 0, 101%)",
+        "hsv(0, 101, 100)",
+        "hsl(0, 100%, 200%)",
+    ] {
+        assert_eq!(parse(s), Err(Error::OutOfRange), "{s}");
+    }
+}
+
+#[test]
+fn malformed() {
+    for s in [
+        "rgb",
+        "rgb()",
+        "rgb(1, 2)",
+        "rgb(1, 2, 3, 4)",
+        "rgb(1, 2, 3",
+        "rgb 1 2 3",
+        "rgba(1, 2, 3)",
+        "rgb(-1, 2, 3)",
+        "rgb(1.5, 2, 3)",
+        "rgb(a, b, c)",
+        "hsv(-30, 100, 100)",
+        "hsl(0rad, 100%, 50%)",
+    ] {
+        assert_eq!(parse(s), Err(Error::BadArguments), "{s}");
+    }
+}
+
+#[test]
+fn display_round_trip() {
+    assert_eq!(rgb(255, 128, 0).to_string(), "#ff8000");
+    assert_eq!(Color::BLACK.to_string(), "#000000");
+    for r in (0..=255).step_by(5) {
+        for g in (0..=255).step_by(3) {
+            for b in [0, 1, 127, 254, 255] {
+                let color = rgb(r, g, b);
+                assert_eq!(parse(&color.to_string()), Ok(color));
+            }
+        }
+    }
+}
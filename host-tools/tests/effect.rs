@@ -0,0 +1,40 @@
+use tp_led_matrix::effect::{Effect, Fire, Plasma};
+use tp_led_matrix::{Instant, PanelImage};
+
+/// Milliseconds of the monotonic timer 49.7 days after the start, when a
+/// 32 bits count would wrap around
+const WRAP_MS: u64 = 1 << 32;
+
+fn render(effect: &mut impl Effect, t_ms: u64) -> PanelImage {
+    let mut image = PanelImage::default();
+    effect.render(Instant::from_ticks(t_ms), &mut image);
+    return image;
+}
+
+#[test]
+fn phase_goes_on_after_weeks() {
+    let mut plasma = Plasma::new();
+    let period = plasma.period_ms as u64;
+    for t_ms in [0, WRAP_MS - period / 2, 100 * WRAP_MS] {
+        assert_eq!(
+            render(&mut plasma, t_ms).as_bytes(),
+            render(&mut plasma, t_ms + period).as_bytes()
+        );
+    }
+}
+
+/// A fire lit just before a 32 bits count would wrap around goes through the
+/// same steps as one lit at the start
+#[test]
+fn steps_go_on_after_weeks() {
+    let (mut early, mut late): (Fire, Fire) = (Fire::new(202), Fire::new(202));
+    let step = early.step_ms as u64;
+    let start = WRAP_MS - step / 2;
+    let first = render(&mut late, start);
+    assert_eq!(render(&mut early, 0).as_bytes(), first.as_bytes());
+    for n in [1, 3, 4] {
+        let image = render(&mut late, start + n * step);
+        assert_eq!(render(&mut early, n * step).as_bytes(), image.as_bytes());
+        assert_ne!(image.as_bytes(), first.as_bytes());
+    }
+}
@@ -0,0 +1,177 @@
+use tp_led_matrix::gif::{Decoder, Disposal, Error, GifFrame};
+use tp_led_matrix::Color;
+
+const PALETTE: [Color; 4] = [
+    Color { r: 0, g: 0, b: 0 },
+    Color { r: 255, g: 0, b: 0 },
+    Color { r: 0, g: 255, b: 0 },
+    Color { r: 0, g: 0, b: 255 },
+];
+
+/// An image of a GIF, and the graphic control extension before it
+struct Image {
+    left: u16,
+    width: u16,
+    indices: &'static [u8],
+    disposal: u8,
+    delay_cs: u16,
+    transparent: Option<u8>,
+}
+
+/// LZW data of `indices` with 2 bits codes, each index being sent as is with
+/// a clear code every 2 indices so that codes stay 3 bits long
+fn lzw(indices: &[u8]) -> Vec<u8> {
+    let (clear, end) = (4, 5);
+    let mut codes = Vec::new();
+    for pair in indices.chunks(2) {
+        codes.push(clear);
+        codes.extend_from_slice(pair);
+    }
+    codes.push(end);
+    let mut data = vec![0u8; (codes.len() * 3).div_ceil(8)];
+    for (i, &code) in codes.iter().enumerate() {
+        for bit in 0..3 {
+            if code & (1 << bit) != 0 {
+                data[(3 * i + bit) / 8] |= 1 << ((3 * i + bit) % 8);
+            }
+        }
+    }
+    return data;
+}
+
+/// GIF89a of a `width` x 1 screen with `PALETTE` as global colour table and
+/// black as background, looping forever
+fn gif(width: u16, images: &[Image]) -> Vec<u8> {
+    let mut data = b"GIF89a".to_vec();
+    data.extend_from_slice(&width.to_le_bytes());
+    data.extend_from_slice(&[1, 0, 0x81, 0, 0]);
+    for color in PALETTE {
+        data.extend_from_slice(&[color.r, color.g, color.b]);
+    }
+    data.extend_from_slice(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+    for image in images {
+        let transparent = image.transparent.is_some() as u8;
+        data.extend_from_slice(&[0x21, 0xf9, 4, image.disposal << 2 | transparent]);
+        data.extend_from_slice(&image.delay_cs.to_le_bytes());
+        data.extend_from_slice(&[image.transparent.unwrap_or(0), 0]);
+        data.push(0x2c);
+        for value in [image.left, 0, image.width, 1] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[0, 2]);
+        let compressed = lzw(image.indices);
+        for block in compressed.chunks(255) {
+            data.push(block.len() as u8);
+            data.extend_from_slice(block);
+        }
+        data.push(0);
+    }
+    data.push(0x3b);
+    return data;
+}
+
+fn row(frame: &GifFrame) -> Vec<Color> {
+    return (0..frame.canvas.width)
+        .map(|x| frame.canvas.pixel(x, 0))
+        .collect();
+}
+
+fn disposals() -> Vec<u8> {
+    let image = |left, indices: &'static [u8], disposal, delay_cs, transparent| Image {
+        left,
+        width: indices.len() as u16,
+        indices,
+        disposal,
+        delay_cs,
+        transparent,
+    };
+    return gif(
+        2,
+        &[
+            image(0, &[1, 1], 1, 10, None),
+            image(0, &[2], 2, 20, None),
+            image(1, &[3], 3, 1, None),
+            image(1, &[0], 1, 30, Some(0)),
+        ],
+    );
+}
+
+#[test]
+fn small_gif() {
+    let indices = &[0, 1, 2, 3, 3, 2, 1];
+    let data = gif(
+        7,
+        &[Image {
+            left: 0,
+            width: 7,
+            indices,
+            disposal: 0,
+            delay_cs: 50,
+            transparent: None,
+        }],
+    );
+    let mut decoder = Decoder::new(&data).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (7, 1));
+    let frame = decoder.next().unwrap().unwrap();
+    assert_eq!(decoder.loop_count, Some(0));
+    let expected: Vec<_> = indices.iter().map(|&i| PALETTE[i as usize]).collect();
+    assert_eq!(row(&frame), expected);
+    assert_eq!(frame.delay_ms, 500);
+    assert!(decoder.next().is_none());
+}
+
+#[test]
+fn disposal_and_delays() {
+    let data = disposals();
+    let frames: Vec<_> = Decoder::new(&data).unwrap().map(Result::unwrap).collect();
+    let (black, red, green, blue) = (PALETTE[0], PALETTE[1], PALETTE[2], PALETTE[3]);
+    let rows: Vec<_> = frames.iter().map(row).collect();
+    assert_eq!(
+        rows,
+        [
+            [red, red],
+            [green, red],
+            // The green pixel is cleared to the background
+            [black, blue],
+            // The blue pixel is taken back, and the transparent one leaves
+            // the red one visible
+            [black, red],
+        ]
+    );
+    let disposals: Vec<_> = frames.iter().map(|frame| frame.disposal).collect();
+    assert_eq!(
+        disposals,
+        [
+            Disposal::Keep,
+            Disposal::Background,
+            Disposal::Previous,
+            Disposal::Keep
+        ]
+    );
+    // Delays below 20 ms mean 100 ms, like in browsers
+    let delays: Vec<_> = frames.iter().map(|frame| frame.delay_ms).collect();
+    assert_eq!(delays, [100, 200, 100, 300]);
+}
+
+#[test]
+fn truncated_file() {
+    let data = disposals();
+    for len in 0..data.len() {
+        let error = match Decoder::new(&data[..len]) {
+            Ok(decoder) => decoder.filter_map(Result::err).next(),
+            Err(error) => Some(error),
+        };
+        assert_eq!(error, Some(Error::UnexpectedEnd), "cut at {len}");
+    }
+}
+
+#[test]
+fn too_large() {
+    let mut data = gif(1, &[]);
+    data[6..10].copy_from_slice(&[0xff; 4]);
+    assert_eq!(Decoder::new(&data).err(), Some(Error::TooLarge));
+    assert_eq!(
+        Decoder::new(&gif(1024, &[])).map(|decoder| decoder.width()),
+        Ok(1024)
+    );
+}
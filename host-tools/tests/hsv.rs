@@ -0,0 +1,74 @@
+use tp_led_matrix::hsv::{Hsl, Hsv, HUE_STEPS};
+use tp_led_matrix::Color;
+
+/// Every colour there is
+fn colors() -> impl Iterator<Item = Color> {
+    return (0..=255)
+        .flat_map(|r| (0..=255).flat_map(move |g| (0..=255).map(move |b| Color { r, g, b })));
+}
+
+#[test]
+fn primaries_on_the_wheel() {
+    assert_eq!(Color::from_hue(0), Color::RED);
+    assert_eq!(Color::from_hue(255), Color::YELLOW);
+    assert_eq!(Color::from_hue(510), Color::GREEN);
+    assert_eq!(Color::from_hue(765), Color::CYAN);
+    assert_eq!(Color::from_hue(1020), Color::BLUE);
+    assert_eq!(Color::from_hue(1275), Color::MAGENTA);
+    assert_eq!(Color::from_hue(HUE_STEPS), Color::RED);
+}
+
+#[test]
+fn hsv_round_trip_over_every_hue() {
+    for h in 0..HUE_STEPS {
+        let hsv = Hsv { h, s: 255, v: 255 };
+        let color = Color::from(hsv);
+        assert_eq!(color.to_hsv(), hsv);
+        assert_eq!(Color::from_hue(h), color);
+        // One step changes a single channel by one unit
+        let next = Color::from_hue(h + 1);
+        let diff = color.r.abs_diff(next.r) + color.g.abs_diff(next.g) + color.b.abs_diff(next.b);
+        assert_eq!(diff, 1, "hue {h}");
+    }
+}
+
+#[test]
+fn hsl_round_trip_over_every_hue() {
+    for h in 0..HUE_STEPS {
+        let hsl = Hsl { h, s: 255, l: 128 };
+        let color = Color::from(hsl);
+        assert_eq!(color, Color::from_hue(h));
+        assert_eq!(color.to_hsl(), hsl);
+    }
+}
+
+#[test]
+fn colors_survive_hsv() {
+    for color in colors() {
+        assert_eq!(Color::from(color.to_hsv()), color);
+    }
+}
+
+#[test]
+fn colors_survive_hsl() {
+    for color in colors() {
+        assert_eq!(Color::from(color.to_hsl()), color);
+    }
+}
+
+#[test]
+fn greys_have_no_hue() {
+    for v in 0..=255 {
+        let grey = Color { r: v, g: v, b: v };
+        assert_eq!(grey.to_hsv(), Hsv { h: 0, s: 0, v });
+        assert_eq!(grey.to_hsl(), Hsl { h: 0, s: 0, l: v });
+    }
+}
+
+#[test]
+fn rotation_wraps() {
+    assert_eq!(Color::RED.rotate_hue(510), Color::GREEN);
+    assert_eq!(Color::BLUE.rotate_hue(HUE_STEPS - 1020), Color::RED);
+    let dim = Color { r: 100, g: 0, b: 0 };
+    assert_eq!(dim.rotate_hue(1020), Color { r: 0, g: 0, b: 100 });
+}
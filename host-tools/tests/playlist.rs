@@ -0,0 +1,62 @@
+use tp_led_matrix::animations::{ANIMATIONS, HEART, SPINNER};
+use tp_led_matrix::playlist::{Command, Playlist, Scheduler};
+use tp_led_matrix::screensaver::STEP_MS;
+use tp_led_matrix::{Instant, PanelImage};
+
+/// Milliseconds of the monotonic timer 49.7 days after the start, when a
+/// 32 bits count would wrap around
+const WRAP_MS: u64 = 1 << 32;
+
+fn command(line: &str) -> Command {
+    return line.parse().unwrap();
+}
+
+/// Frames of a clock set to `time` at `set_ms`, shown for 6 s from 5 minutes
+/// later
+fn clock_frames(time: &str, set_ms: u64) -> Vec<Vec<u8>> {
+    let mut playlist = Playlist::new();
+    playlist.apply(&command("add 60s cut clock")).unwrap();
+    let mut scheduler = Scheduler::new(playlist, &[], 1);
+    let set = Instant::from_ticks(set_ms);
+    scheduler
+        .apply(&command(&format!("time {time}")), set)
+        .unwrap();
+    let from_ms = set_ms + 5 * 60_000;
+    return (0..100)
+        .map(|i| {
+            let mut image = PanelImage::default();
+            let t = Instant::from_ticks(from_ms + i * STEP_MS as u64);
+            scheduler.render(t, &mut image);
+            return image.as_bytes().to_vec();
+        })
+        .collect();
+}
+
+#[test]
+fn clock_keeps_time_after_weeks() {
+    let frames = clock_frames("12:34", 0);
+    assert_ne!(frames, clock_frames("12:35", 0));
+    assert_eq!(frames, clock_frames("12:34", WRAP_MS - 1000));
+    assert_eq!(frames, clock_frames("12:34", 100 * WRAP_MS));
+}
+
+#[test]
+fn animations_are_played() {
+    let mut playlist = Playlist::new();
+    for line in [
+        "add x1 cut animation 0",
+        "add x1 cut animation 7",
+        "add x1 cut animation 1",
+    ] {
+        playlist.apply(&command(line)).unwrap();
+    }
+    let mut scheduler = Scheduler::new(playlist, &ANIMATIONS, 1);
+    let mut image = PanelImage::default();
+    let mut t_ms = 0;
+    // Missing animations are skipped
+    for frame in HEART.iter().chain(&SPINNER) {
+        scheduler.render(Instant::from_ticks(t_ms), &mut image);
+        assert_eq!(image.as_bytes(), frame.image.as_bytes());
+        t_ms += frame.delay_ms as u64;
+    }
+}
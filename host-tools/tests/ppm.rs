@@ -0,0 +1,167 @@
+use tp_led_matrix::ppm::{self, Error, Format, Frames};
+use tp_led_matrix::{Color, Image};
+
+/// Image where every pixel and channel differs
+fn sample() -> Image {
+    let mut image = Image::default();
+    for y in 0..8 {
+        for x in 0..8 {
+            image[(y, x)] = Color {
+                r: (x * 32 + y) as u8,
+                g: (y * 32 + x) as u8,
+                b: 255 - (x * 8 + y) as u8 * 3,
+            };
+        }
+    }
+    return image;
+}
+
+fn bytes(image: &Image) -> [u8; 192] {
+    return *image.as_ref();
+}
+
+fn channels(color: Color) -> (u8, u8, u8) {
+    return (color.r, color.g, color.b);
+}
+
+fn round_trip(format: Format) {
+    let image = sample();
+    let mut out = [0; ppm::MAX_ASCII_PPM_LEN];
+    let len = ppm::encode(&image, format, &mut out).unwrap();
+    let (decoded, consumed) = ppm::decode(&out[..len]).unwrap();
+    // The plain format ends with a newline, which is left to the next image
+    assert!(out[consumed..len].iter().all(u8::is_ascii_whitespace));
+    assert_eq!(bytes(&decoded), bytes(&image));
+}
+
+#[test]
+fn ascii_round_trip() {
+    round_trip(Format::Ascii);
+}
+
+#[test]
+fn binary_round_trip() {
+    round_trip(Format::Binary);
+}
+
+#[test]
+fn pam_round_trip() {
+    round_trip(Format::Pam);
+}
+
+#[test]
+fn encoded_lengths() {
+    let mut out = [0; ppm::MAX_ASCII_PPM_LEN];
+    let binary = ppm::encode(&sample(), Format::Binary, &mut out).unwrap();
+    assert_eq!(binary, ppm::BINARY_PPM_LEN);
+    let pam = ppm::encode(&sample(), Format::Pam, &mut out).unwrap();
+    assert_eq!(pam, ppm::PAM_LEN);
+    let white = Image::new_solid(Color {
+        r: 255,
+        g: 255,
+        b: 255,
+    });
+    let ascii = ppm::encode(&white, Format::Ascii, &mut out).unwrap();
+    assert_eq!(ascii, ppm::MAX_ASCII_PPM_LEN);
+    assert_eq!(
+        ppm::encode(&white, Format::Ascii, &mut out[..ascii - 1]),
+        Err(Error::BufferTooSmall)
+    );
+}
+
+#[test]
+fn stream_of_frames() {
+    let mut data = vec![0; ppm::BINARY_PPM_LEN + ppm::PAM_LEN];
+    let len = ppm::encode(&sample(), Format::Binary, &mut data).unwrap();
+    ppm::encode(&Image::default(), Format::Pam, &mut data[len..]).unwrap();
+    data.extend_from_slice(b"\n\n");
+    let frames: Vec<_> = Frames::new(&data).collect();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(bytes(frames[0].as_ref().unwrap()), bytes(&sample()));
+    assert_eq!(bytes(frames[1].as_ref().unwrap()), [0; 192]);
+}
+
+#[test]
+fn comments_and_maxval() {
+    let mut data = b"P3 # plain\n8 8\n# samples up to 1\n1\n".to_vec();
+    for i in 0..64 {
+        data.extend_from_slice(if i % 2 == 0 { b"1 0 1\n" } else { b"0 1 0\n" });
+    }
+    let (image, _) = ppm::decode(&data).unwrap();
+    assert_eq!(channels(image[(0, 0)]), (255, 0, 255));
+    assert_eq!(channels(image[(0, 1)]), (0, 255, 0));
+}
+
+#[test]
+fn pam_with_alpha() {
+    let mut data =
+        b"P7\nWIDTH 8\nHEIGHT 8\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n".to_vec();
+    for _ in 0..64 {
+        data.extend_from_slice(&[255, 100, 0, 51]);
+    }
+    let (image, _) = ppm::decode(&data).unwrap();
+    assert_eq!(channels(image[(7, 7)]), (51, 20, 0));
+}
+
+#[test]
+fn wrong_dimensions() {
+    let mut data = b"P6\n16 8\n255\n".to_vec();
+    data.resize(data.len() + 16 * 8 * 3, 0);
+    assert_eq!(
+        ppm::decode(&data).err(),
+        Some(Error::BadDimensions {
+            width: 16,
+            height: 8
+        })
+    );
+    let data = b"P7\nWIDTH 8\nHEIGHT 7\nDEPTH 3\nMAXVAL 255\nENDHDR\n";
+    assert_eq!(
+        ppm::decode(data).err(),
+        Some(Error::BadDimensions {
+            width: 8,
+            height: 7
+        })
+    );
+}
+
+#[test]
+fn truncated_input() {
+    let mut out = [0; ppm::MAX_ASCII_PPM_LEN];
+    for format in [Format::Ascii, Format::Binary, Format::Pam] {
+        let len = ppm::encode(&sample(), format, &mut out).unwrap();
+        // Cut in the header, then in the raster
+        for cut in [1, 8, len / 2] {
+            assert_eq!(
+                ppm::decode(&out[..cut]).err(),
+                Some(Error::UnexpectedEnd),
+                "{format:?} cut at {cut}"
+            );
+        }
+    }
+}
+
+#[test]
+fn malformed_headers() {
+    assert_eq!(ppm::decode(b"P5\n8 8\n255\n").err(), Some(Error::BadMagic));
+    assert_eq!(ppm::decode(b"P6\n8 x\n255\n").err(), Some(Error::BadHeader));
+    assert_eq!(
+        ppm::decode(b"P6\n8 8\n256\n").err(),
+        Some(Error::BadMaxval(256))
+    );
+    assert_eq!(
+        ppm::decode(b"P6\n8 8\n0\n").err(),
+        Some(Error::BadMaxval(0))
+    );
+    assert_eq!(
+        ppm::decode(b"P7\nWIDTH 8\nHEIGHT 8\nDEPTH 1\nMAXVAL 255\nENDHDR\n").err(),
+        Some(Error::BadDepth(1))
+    );
+    assert_eq!(
+        ppm::decode(b"P7\nWIDTH 8\nWIDTH 8\n").err(),
+        Some(Error::BadHeader)
+    );
+    assert_eq!(
+        ppm::decode(b"P3\n8 8\n15\n16").err(),
+        Some(Error::BadSample)
+    );
+}
@@ -0,0 +1,76 @@
+use tp_led_matrix::correction::{Error, Profile, Setting, ONE};
+use tp_led_matrix::transform::Rotation;
+
+#[test]
+fn settings_are_parsed() {
+    assert_eq!("rotate 90".parse(), Ok(Setting::Rotation(Rotation::Deg90)));
+    assert_eq!("mirror on".parse(), Ok(Setting::Mirrored(true)));
+    assert_eq!(
+        "gain 1 0.9 0.75".parse(),
+        Ok(Setting::Gain([ONE, 230, 192]))
+    );
+    assert_eq!(" temperature 5000 ".parse(), Ok(Setting::Temperature(5000)));
+    assert_eq!("matrix off".parse(), Ok(Setting::Matrix(None)));
+    assert_eq!(
+        "matrix 1 0 0  0 1 0  -0.5 0 1.5".parse(),
+        Ok(Setting::Matrix(Some([
+            [256, 0, 0],
+            [0, 256, 0],
+            [-128, 0, 384]
+        ])))
+    );
+    assert_eq!("reset".parse(), Ok(Setting::Reset));
+}
+
+#[test]
+fn bad_settings_are_rejected() {
+    assert_eq!("tilt 3".parse::<Setting>(), Err(Error::UnknownSetting));
+    assert_eq!("rotate 45".parse::<Setting>(), Err(Error::BadArguments));
+    assert_eq!("mirror".parse::<Setting>(), Err(Error::BadArguments));
+    assert_eq!("reset now".parse::<Setting>(), Err(Error::BadArguments));
+    assert_eq!("gain 1 1".parse::<Setting>(), Err(Error::BadArguments));
+    assert_eq!("gain 1 1 1 1".parse::<Setting>(), Err(Error::BadArguments));
+    assert_eq!("gain 1 -1 1".parse::<Setting>(), Err(Error::BadValue));
+    assert_eq!("gain 1 x 1".parse::<Setting>(), Err(Error::BadValue));
+    assert_eq!("gain 1 .5 1".parse::<Setting>(), Err(Error::BadValue));
+    assert_eq!("temperature 500".parse::<Setting>(), Err(Error::BadValue));
+    assert_eq!(
+        "matrix 1 0 0 0 1 0 0 0 200".parse::<Setting>(),
+        Err(Error::BadValue)
+    );
+}
+
+#[test]
+fn settings_change_one_field() {
+    let mut profile = Profile::NEUTRAL;
+    profile.apply(&Setting::Temperature(4000));
+    profile.apply(&Setting::Rotation(Rotation::Deg270));
+    profile.apply(&Setting::Gain([ONE, ONE, 200]));
+    assert_eq!(profile.correction.temperature, 4000);
+    assert_eq!(profile.correction.gain, [ONE, ONE, 200]);
+    assert_eq!(profile.orientation.rotation, Rotation::Deg270);
+    assert!(!profile.orientation.mirrored);
+    profile.apply(&Setting::Reset);
+    assert_eq!(profile, Profile::NEUTRAL);
+}
+
+#[test]
+fn bytes_round_trip() {
+    let mut profile = Profile::NEUTRAL;
+    for setting in [
+        "rotate 180",
+        "mirror on",
+        "gain 1.2 1 0.8",
+        "temperature 7250",
+        "matrix 1 0.1 0 0 1 0 -0.1 0 1",
+    ] {
+        profile.apply(&setting.parse().unwrap());
+        assert_eq!(Profile::from_bytes(&profile.to_bytes()), Ok(profile));
+    }
+    let mut bytes = profile.to_bytes();
+    assert_eq!(Profile::from_bytes(&bytes[1..]), Err(Error::BadLength));
+    bytes[2] = 2;
+    assert_eq!(Profile::from_bytes(&bytes), Err(Error::BadValue));
+    bytes[0] = 9;
+    assert_eq!(Profile::from_bytes(&bytes), Err(Error::BadVersion(9)));
+}
@@ -0,0 +1,153 @@
+use tp_led_matrix::gamma::GAMMA_TAB;
+use tp_led_matrix::scale::{self, Dither, Filter, Fit, Options, RgbImage};
+use tp_led_matrix::{Color, Image};
+
+const BLACK: Color = Color { r: 0, g: 0, b: 0 };
+
+fn bytes(image: &Image) -> [u8; 192] {
+    return *image.as_ref();
+}
+
+/// Column and row of every pixel, row by row
+fn positions() -> impl Iterator<Item = (usize, usize)> {
+    return (0..8).flat_map(|y| (0..8).map(move |x| (x, y)));
+}
+
+#[test]
+fn empty_source_gives_background() {
+    for (width, height) in [(0, 0), (0, 5), (5, 0)] {
+        let src = RgbImage::new(width, height, Color::RED);
+        for filter in [Filter::Nearest, Filter::Bilinear, Filter::Area] {
+            for fit in [
+                Fit::Stretch,
+                Fit::Fit,
+                Fit::Fill,
+                Fit::Crop {
+                    x: 0,
+                    y: 0,
+                    width: 1,
+                    height: 1,
+                },
+            ] {
+                let options = Options {
+                    filter,
+                    fit,
+                    background: Color::BLUE,
+                    ..Options::default()
+                };
+                let image = scale::to_image(&src, &options);
+                assert_eq!(bytes(&image), bytes(&Image::new_solid(Color::BLUE)));
+            }
+        }
+    }
+}
+
+#[test]
+fn short_data_gives_background() {
+    let mut src = RgbImage::new(4, 4, Color::RED);
+    src.data.truncate(10);
+    let image = scale::to_image(&src, &Options::default());
+    assert_eq!(bytes(&image), [0; 192]);
+}
+
+#[test]
+fn solid_source_stays_solid() {
+    let src = RgbImage::new(13, 5, Color::GREEN);
+    for filter in [Filter::Nearest, Filter::Bilinear, Filter::Area] {
+        let options = Options {
+            filter,
+            fit: Fit::Stretch,
+            ..Options::default()
+        };
+        let image = scale::to_image(&src, &options);
+        assert_eq!(bytes(&image), bytes(&Image::new_solid(Color::GREEN)));
+    }
+}
+
+/// Mean LED intensity of the red channel
+fn mean_intensity(image: &Image) -> f32 {
+    let sum: u32 = positions()
+        .map(|(x, y)| GAMMA_TAB[image[(y, x)].r as usize] as u32)
+        .sum();
+    return sum as f32 / 64.0;
+}
+
+/// A 16x16 gradient whose 2x2 blocks average to a quarter above a whole
+/// value, which rounding alone always loses
+#[test]
+fn dithering_diffuses_the_error() {
+    let mut src = RgbImage::new(16, 16, BLACK);
+    for y in 0..16 {
+        for x in 0..16 {
+            let level = 100 + 12 * (x / 2) as u8 + (x % 2 == 1 && y % 2 == 1) as u8;
+            src.set_pixel(x, y, Color { r: level, ..BLACK });
+        }
+    }
+    let target: f32 = (0..8)
+        .map(|col| {
+            let low = GAMMA_TAB[100 + 12 * col] as f32;
+            let high = GAMMA_TAB[101 + 12 * col] as f32;
+            return low + (high - low) * 0.25;
+        })
+        .sum::<f32>()
+        / 8.0;
+    let render = |dither| {
+        let options = Options {
+            fit: Fit::Stretch,
+            dither,
+            ..Options::default()
+        };
+        return scale::to_image(&src, &options);
+    };
+    let (rounded, dithered) = (render(Dither::None), render(Dither::FloydSteinberg));
+    for (x, y) in positions() {
+        assert_eq!(rounded[(y, x)].r, 100 + 12 * x as u8);
+    }
+    assert_ne!(bytes(&dithered), bytes(&rounded));
+    let rounding_error = (mean_intensity(&rounded) - target).abs();
+    let dithering_error = (mean_intensity(&dithered) - target).abs();
+    assert!(
+        dithering_error < rounding_error / 4.0,
+        "{dithering_error} is not much below {rounding_error}"
+    );
+    // Every pixel stays at one of the two levels around its value
+    for (x, y) in positions() {
+        let low = 100 + 12 * x;
+        let level = GAMMA_TAB[dithered[(y, x)].r as usize];
+        assert!(level >= GAMMA_TAB[low] && level <= GAMMA_TAB[low + 1]);
+    }
+}
+
+/// Which rows show the red picture
+fn picture_rows(image: &Image) -> Vec<bool> {
+    return (0..8).map(|y| image.row(y).contains(&Color::RED)).collect();
+}
+
+#[test]
+fn odd_aspect_ratios_fill_the_borders() {
+    let options = Options {
+        background: Color::BLUE,
+        ..Options::default()
+    };
+    // 5x2 is scaled to 8x3, centred vertically
+    let wide = scale::to_image(&RgbImage::new(5, 2, Color::RED), &options);
+    let rows = picture_rows(&wide);
+    assert_eq!(rows, [false, false, true, true, true, false, false, false]);
+    for (x, y) in positions() {
+        let expected = if rows[y] { Color::RED } else { Color::BLUE };
+        assert_eq!(wide[(y, x)], expected);
+    }
+    // 1x7 is scaled to 1x8, centred horizontally
+    let tall = scale::to_image(&RgbImage::new(1, 7, Color::RED), &options);
+    for (x, y) in positions() {
+        let expected = if x == 3 { Color::RED } else { Color::BLUE };
+        assert_eq!(tall[(y, x)], expected);
+    }
+    // Filling leaves no border
+    let options = Options {
+        fit: Fit::Fill,
+        ..options
+    };
+    let filled = scale::to_image(&RgbImage::new(5, 2, Color::RED), &options);
+    assert_eq!(bytes(&filled), bytes(&Image::new_solid(Color::RED)));
+}
@@ -0,0 +1,26 @@
+use tp_led_matrix::font::Typeface;
+use tp_led_matrix::fonts::FONT_6X8;
+use tp_led_matrix::hsv::HUE_STEPS;
+use tp_led_matrix::scroller::{Repeat, TextScroller};
+
+/// The rainbow goes on until the frame counter wraps, and comes back to the
+/// same colours every `HUE_STEPS` frames
+#[test]
+fn rainbow_at_any_frame() {
+    let scroller = TextScroller {
+        markup: true,
+        // So slow that the text stays against the left edge
+        repeat: Repeat::Bounce,
+        frames_per_pixel: u32::MAX,
+        ..TextScroller::new("{rainbow}Hello", Typeface::Proportional(&FONT_6X8))
+    };
+    let render = |frame| {
+        return scroller.render::<8, 8>(frame);
+    };
+    let steps = HUE_STEPS as u32;
+    for frame in [0, 1, 1000, u32::MAX / 2, u32::MAX - 2 * steps] {
+        assert_eq!(render(frame).as_bytes(), render(frame + steps).as_bytes());
+    }
+    assert_ne!(render(0).as_bytes(), render(1).as_bytes());
+    render(u32::MAX);
+}
@@ -0,0 +1,35 @@
+use tp_led_matrix::sprite::Bitmap;
+use tp_led_matrix::{Color, PanelImage};
+
+const PALETTE: [Color; 2] = [Color::RED, Color::BLUE];
+
+#[test]
+fn bitmap_is_drawn_where_placed() {
+    let bitmap = Bitmap::new(2, 2, &[1, 0, 0, 2]);
+    let mut image = PanelImage::default();
+    // Only the bottom left pixel lands on the image, and it is transparent
+    bitmap.draw(&PALETTE, 7, -1, &mut image);
+    assert_eq!(image.as_bytes(), PanelImage::default().as_bytes());
+    bitmap.draw(&PALETTE, 3, 4, &mut image);
+    assert_eq!(image[(4, 3)], Color::RED);
+    assert_eq!(image[(4, 4)], Color::BLACK);
+    assert_eq!(image[(5, 4)], Color::BLUE);
+}
+
+#[test]
+#[should_panic]
+fn zero_width_is_rejected() {
+    Bitmap::new(0, 2, &[]);
+}
+
+#[test]
+fn zero_width_draws_nothing() {
+    let bitmap = Bitmap {
+        width: 0,
+        height: 1,
+        pixels: &[1, 2],
+    };
+    let mut image = PanelImage::default();
+    bitmap.draw(&PALETTE, 0, 0, &mut image);
+    assert_eq!(image.as_bytes(), PanelImage::default().as_bytes());
+}
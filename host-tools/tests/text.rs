@@ -0,0 +1,102 @@
+use std::fs;
+use tp_led_matrix::font::{self, Font};
+use tp_led_matrix::fonts::{FONT_3X5, FONT_5X7, FONT_6X8};
+use tp_led_matrix::Color;
+use tp_led_matrix_host::text::Strip;
+
+const FONTS: [(&str, &Font); 3] = [("3x5", &FONT_3X5), ("5x7", &FONT_5X7), ("6x8", &FONT_6X8)];
+
+/// Accented letters of French, drawn by the fonts themselves
+const ACCENTS: &str = "àâäçéèêëîïôöùûüÿæœ ÀÂÄÇÉÈÊËÎÏÔÖÙÛÜŸÆŒ";
+/// Letters the fonts lack, drawn as their ASCII look-alikes
+const FALLBACK: &str = "ŁĄŚŻ łąśż “ő” – Ŭ…";
+/// Characters without glyph nor substitute, drawn as U+FFFD
+const REPLACEMENT: &str = "日本\u{fffd}";
+/// Every icon, and the emoji standing for them
+const ICONS: &str = "\u{e000}\u{e001}\u{e002}\u{e003}\u{e004}\u{e005}\u{e006}\u{e007}\u{e008}\u{e009}\u{e00a}\u{e00b} ♥★☺♪☀✓✕⚠↑↓←→";
+
+/// Compare the rendering of `text` with every font against
+/// `tests/fixtures/<name>-<font>.ppm`, which are written instead when
+/// `UPDATE_FIXTURES` is set, after a deliberate change to the fonts
+fn check(name: &str, text: &str) {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+    for (font_name, font) in FONTS {
+        let path = format!("{dir}/{name}-{font_name}.ppm");
+        let ppm = Strip::text(text, font, Color::WHITE).to_ppm();
+        if std::env::var_os("UPDATE_FIXTURES").is_some() {
+            fs::write(&path, &ppm).unwrap();
+            continue;
+        }
+        let reference = fs::read(&path).unwrap_or_else(|e| panic!("{path}: {e}"));
+        assert!(
+            reference == ppm,
+            "{text:?} with {font_name} differs from {path}"
+        );
+    }
+}
+
+#[test]
+fn accents() {
+    check("accents", ACCENTS);
+}
+
+#[test]
+fn ascii_fallback() {
+    for (_, font) in FONTS {
+        assert!(font.covers(FALLBACK));
+    }
+    check("fallback", FALLBACK);
+}
+
+#[test]
+fn replacement_character() {
+    for (_, font) in FONTS {
+        assert!(!font.covers("日本"));
+        assert_eq!(font.glyph('日'), font.glyph(char::REPLACEMENT_CHARACTER));
+    }
+    check("replacement", REPLACEMENT);
+}
+
+#[test]
+fn icons() {
+    for (_, font) in FONTS {
+        assert!(font.covers(ICONS));
+        assert_eq!(font.glyph('♥'), font.glyph(font::ICON_HEART));
+    }
+    check("icons", ICONS);
+}
+
+/// Letters differing only by their accent must not be drawn the same
+#[test]
+fn accents_are_distinct() {
+    let families =
+        "AÀÁÂÃÄ aàáâãä EÈÉÊË eèéêë IÌÍÎÏ iìíîï OÒÓÔÕÖ oòóôõö UÙÚÛÜ uùúûü YÝŸ yýÿ NÑ nñ CÇ cç æœ ÆŒ";
+    for (font_name, font) in FONTS {
+        for family in families.split(' ') {
+            let letters: Vec<char> = family.chars().collect();
+            for (i, &a) in letters.iter().enumerate() {
+                for &b in &letters[i + 1..] {
+                    assert_ne!(font.glyph(a), font.glyph(b), "{a} and {b} with {font_name}");
+                }
+            }
+        }
+    }
+}
+
+/// The characters just before the first of a table are not in it, even
+/// where computing their index would overflow on the board
+#[test]
+fn below_first_of_tables() {
+    for (font_name, font) in FONTS {
+        for table in font.tables {
+            let Some(below) = char::from_u32(table.first as u32 - 1) else {
+                continue;
+            };
+            assert_eq!(table.get(below), None, "{below:?} with {font_name}");
+            assert!(table.get(table.first).is_some(), "{font_name}");
+        }
+        for c in ['\u{1f}', 'ő', '\u{177}', '\u{20ab}', '\u{fffc}'] {
+            assert_eq!(font.get(c), None, "{c:?} with {font_name}");
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use tp_led_matrix::timeline::{Easing, Keyframe, State, Timeline, Track};
+use tp_led_matrix::{Duration, Instant};
+
+/// Milliseconds of the monotonic timer 49.7 days after the start, when a
+/// 32 bits count would wrap around
+const WRAP_MS: u64 = 1 << 32;
+
+const OFFSETS: [Keyframe<i32>; 3] = [
+    Keyframe::new(0, 0, Easing::Linear),
+    Keyframe::new(1000, 100, Easing::Linear),
+    Keyframe::new(2000, 0, Easing::Step),
+];
+
+fn timeline(start_ms: u64) -> Timeline<'static> {
+    let mut timeline = Timeline::new(Instant::from_ticks(start_ms));
+    timeline.offset = Track::new(&OFFSETS);
+    return timeline;
+}
+
+fn offset_at(timeline: &Timeline, t_ms: u64) -> i32 {
+    return timeline.at(Instant::from_ticks(t_ms)).offset;
+}
+
+#[test]
+fn track_values() {
+    let track = Track::new(&OFFSETS);
+    let value = |ms| track.value_at(Duration::from_ticks(ms));
+    assert_eq!(value(0), Some(0));
+    assert_eq!(value(250), Some(25));
+    assert_eq!(value(1000), Some(100));
+    assert_eq!(value(1999), Some(100));
+    assert_eq!(value(2000), Some(0));
+    assert_eq!(value(WRAP_MS + 250), Some(0));
+    let looping = Track {
+        looping: true,
+        ..track
+    };
+    assert_eq!(
+        looping.value_at(Duration::from_ticks(WRAP_MS * 2000 + 250)),
+        Some(25)
+    );
+    assert_eq!(
+        Track::<i32>::empty().value_at(Duration::from_ticks(0)),
+        None
+    );
+}
+
+/// A timeline started just before the monotonic timer would have wrapped on
+/// 32 bits goes on as if it started at 0
+#[test]
+fn timeline_goes_on_after_weeks() {
+    let start_ms = WRAP_MS - 500;
+    let (early, late) = (timeline(0), timeline(start_ms));
+    for ms in [0, 250, 500, 750, 1000, 1999, 2000, 5000] {
+        assert_eq!(
+            offset_at(&early, ms),
+            offset_at(&late, start_ms + ms),
+            "{ms}"
+        );
+    }
+    assert!(!late.is_finished(Instant::from_ticks(start_ms + 1999)));
+    assert!(late.is_finished(Instant::from_ticks(start_ms + 2000)));
+    // Before the start, it waits at the beginning
+    assert!(!late.is_finished(Instant::from_ticks(0)));
+    assert_eq!(offset_at(&late, 0), 0);
+    assert_eq!(
+        timeline(0).at(Instant::from_ticks(0)).color,
+        State::default().color
+    );
+}
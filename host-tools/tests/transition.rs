@@ -0,0 +1,35 @@
+use tp_led_matrix::transition::{Kind, Transition, Transitioner};
+use tp_led_matrix::{Color, PanelImage};
+
+/// The images sent during a transition without one of their own, such as
+/// the frames of the screensaver, do not stop it but become its target
+#[test]
+fn cut_keeps_transition_running() {
+    let black = PanelImage::new_solid(Color::BLACK);
+    let white = PanelImage::new_solid(Color::WHITE);
+    let red = PanelImage::new_solid(Color::RED);
+    let mut transitioner = Transitioner::new();
+    transitioner.start(Transition::new(Kind::Crossfade, 500), &black);
+    transitioner.advance(100_000, &white);
+    assert!(transitioner.image().is_some());
+
+    transitioner.start(Transition::CUT, &white);
+    assert!(transitioner.image().is_some());
+    transitioner.advance(200_000, &red);
+    let image = transitioner.image().unwrap();
+    let pixel = image[(0, 0)];
+    assert!(pixel.r > 0 && pixel.r < 255 && pixel.g == 0, "{pixel:?}");
+
+    transitioner.advance(200_000, &red);
+    assert!(transitioner.image().is_none());
+}
+
+#[test]
+fn cut_alone_is_instantaneous() {
+    let mut transitioner = Transitioner::new();
+    transitioner.start(Transition::CUT, &PanelImage::default());
+    transitioner.advance(1000, &PanelImage::new_solid(Color::WHITE));
+    assert!(transitioner.image().is_none());
+    transitioner.start(Transition::new(Kind::Crossfade, 0), &PanelImage::default());
+    assert!(transitioner.image().is_none());
+}
@@ -1,5 +1,8 @@
 MEMORY
 {
-  FLASH : ORIGIN = 0x08000000, LENGTH = 1M
+  /* The last two 2K pages are kept out of reach of the linker, as storage.rs
+     erases and rewrites them (storage::PLAYLIST_PAGE at 0x080FF000 and
+     storage::PROFILE_PAGE at 0x080FF800) */
+  FLASH : ORIGIN = 0x08000000, LENGTH = 1M - 4K
   RAM   : ORIGIN = 0x20000000, LENGTH = 96K
 }
@@ -0,0 +1,76 @@
+//! Animations bundled with the firmware, which playlist entries refer to by
+//! their index in `ANIMATIONS`
+
+use crate::protocol::Frame;
+use crate::{Color, Image};
+
+/// Image of `color` where the bits of `rows` are set, bit 7 being the left
+/// column, and black elsewhere
+const fn bitmap(rows: [u8; 8], color: Color) -> Image {
+    let mut pixels = [[Color::BLACK; 8]; 8];
+    let mut y = 0;
+    while y < 8 {
+        let mut x = 0;
+        while x < 8 {
+            if rows[y] & (0x80 >> x) != 0 {
+                pixels[y][x] = color;
+            }
+            x += 1;
+        }
+        y += 1;
+    }
+    return Image::from_rows(pixels);
+}
+
+const fn frame(rows: [u8; 8], color: Color, delay_ms: u32) -> Frame {
+    return Frame {
+        image: bitmap(rows, color),
+        delay_ms,
+    };
+}
+
+const BIG_HEART: [u8; 8] = [
+    0b0110_0110,
+    0b1111_1111,
+    0b1111_1111,
+    0b1111_1111,
+    0b0111_1110,
+    0b0011_1100,
+    0b0001_1000,
+    0b0000_0000,
+];
+
+const SMALL_HEART: [u8; 8] = [
+    0b0000_0000,
+    0b0010_0100,
+    0b0111_1110,
+    0b0111_1110,
+    0b0011_1100,
+    0b0001_1000,
+    0b0000_0000,
+    0b0000_0000,
+];
+
+/// Heart beating twice in a row, once a second
+pub const HEART: [Frame; 4] = [
+    frame(BIG_HEART, Color::RED, 150),
+    frame(SMALL_HEART, Color::RED, 150),
+    frame(BIG_HEART, Color::RED, 150),
+    frame(SMALL_HEART, Color::RED, 550),
+];
+
+const VERTICAL: [u8; 8] = [0x18; 8];
+const RISING: [u8; 8] = [0x03, 0x07, 0x0e, 0x1c, 0x38, 0x70, 0xe0, 0xc0];
+const HORIZONTAL: [u8; 8] = [0, 0, 0, 0xff, 0xff, 0, 0, 0];
+const FALLING: [u8; 8] = [0xc0, 0xe0, 0x70, 0x38, 0x1c, 0x0e, 0x07, 0x03];
+
+/// Line turning around the centre of the panel
+pub const SPINNER: [Frame; 4] = [
+    frame(VERTICAL, Color::CYAN, 100),
+    frame(RISING, Color::CYAN, 100),
+    frame(HORIZONTAL, Color::CYAN, 100),
+    frame(FALLING, Color::CYAN, 100),
+];
+
+/// What `animation 0` and `animation 1` show
+pub const ANIMATIONS: [&[Frame]; 2] = [&HEART, &SPINNER];
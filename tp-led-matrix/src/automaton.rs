@@ -0,0 +1,275 @@
+use crate::effect::{steps, Effect};
+use crate::pattern::{color_at, Stop};
+use crate::{Color, Image, Instant};
+use core::fmt;
+use core::str::FromStr;
+
+/// Why a string is not a rule
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Not made of a `B` part and an `S` part separated by `/`
+    BadFormat,
+    /// A neighbour count above 8
+    BadCount,
+}
+
+/// Outer totalistic rule: a dead cell is born when its number of live
+/// neighbours is in `birth`, and a live cell survives when it is in
+/// `survival`, bit n standing for n neighbours
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub birth: u16,
+    pub survival: u16,
+}
+
+impl Rule {
+    /// Conway's Game of Life, B3/S23
+    pub const LIFE: Rule = Rule::new(0b1000, 0b1100);
+    /// B36/S23, where some patterns replicate themselves
+    pub const HIGHLIFE: Rule = Rule::new(0b100_1000, 0b1100);
+    /// B2/S, where every cell dies at once
+    pub const SEEDS: Rule = Rule::new(0b100, 0);
+    /// B3678/S34678, symmetric between live and dead cells
+    pub const DAY_AND_NIGHT: Rule = Rule::new(0b1_1100_1000, 0b1_1101_1000);
+
+    pub const fn new(birth: u16, survival: u16) -> Self {
+        return Rule { birth, survival };
+    }
+
+    /// Whether a cell is alive in the next generation
+    pub fn next(&self, alive: bool, neighbours: u32) -> bool {
+        let counts = if alive { self.survival } else { self.birth };
+        return counts & (1 << neighbours) != 0;
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        return Rule::LIFE;
+    }
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    /// Parse the B/S notation, such as `B3/S23`, whatever the case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let counts = |digits: &str| {
+            let mut bits = 0;
+            for c in digits.chars() {
+                match c.to_digit(10) {
+                    Some(n) if n <= 8 => bits |= 1 << n,
+                    _ => return Err(Error::BadCount),
+                }
+            }
+            return Ok(bits);
+        };
+        let (birth, survival) = s.trim().split_once('/').ok_or(Error::BadFormat)?;
+        let birth = birth.strip_prefix(['B', 'b']).ok_or(Error::BadFormat)?;
+        let survival = survival.strip_prefix(['S', 's']).ok_or(Error::BadFormat)?;
+        return Ok(Rule::new(counts(birth)?, counts(survival)?));
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for n in (0..=8).filter(|n| self.birth & (1 << n) != 0) {
+            write!(f, "{n}")?;
+        }
+        write!(f, "/S")?;
+        for n in (0..=8).filter(|n| self.survival & (1 << n) != 0) {
+            write!(f, "{n}")?;
+        }
+        return Ok(());
+    }
+}
+
+/// Generations remembered to notice that the board repeats itself
+const HISTORY: usize = 16;
+
+/// Generations still shown once the board stagnates or cycles, before it is
+/// seeded again
+pub const LINGER_GENERATIONS: u32 = 12;
+
+/// Colours of the cells from birth to `MAX_AGE` generations old
+const AGES: [Stop; 3] = [
+    Stop::new(0.0, Color::WHITE),
+    Stop::new(0.3, Color::CYAN),
+    Stop::new(1.0, Color::BLUE),
+];
+
+/// Age from which cells keep the same colour
+pub const MAX_AGE: u8 = 32;
+
+/// Cellular automaton on a `W` x `H` torus, whose edges wrap around. Every
+/// cell records how many generations it has been alive for, so that it can
+/// be coloured by age. The board is seeded again from its random generator
+/// when it dies out, stagnates or cycles, so that it goes on forever, always
+/// the same way for a given seed.
+#[derive(Clone, Debug)]
+pub struct Automaton<const W: usize = 8, const H: usize = 8> {
+    pub rule: Rule,
+    /// Colours by age, 0.0 being a newborn cell and 1.0 `MAX_AGE`
+    pub stops: &'static [Stop],
+    /// Share of live cells when seeding, out of 256
+    pub density: u8,
+    /// Time between two generations when used as an `Effect`
+    pub step_ms: u32,
+    /// Age of every cell, 0 for dead ones
+    ages: [[u8; W]; H],
+    rng: u32,
+    /// Hashes of the last generations, in a ring
+    history: [u32; HISTORY],
+    generation: u32,
+    /// Generations left before seeding again
+    linger: Option<u32>,
+    last: Option<Instant>,
+}
+
+impl<const W: usize, const H: usize> Automaton<W, H> {
+    /// Empty board, seeded from `seed` at the first step
+    pub const fn new(rule: Rule, seed: u32) -> Self {
+        return Automaton {
+            rule,
+            stops: &AGES,
+            density: 96,
+            step_ms: 240,
+            ages: [[0; W]; H],
+            // xorshift never leaves 0
+            rng: if seed == 0 { 1 } else { seed },
+            history: [0; HISTORY],
+            generation: 0,
+            linger: None,
+            last: None,
+        };
+    }
+
+    fn random(&mut self) -> u32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        return self.rng;
+    }
+
+    /// Fill the board at random with about `density` live cells out of 256
+    pub fn randomize(&mut self) {
+        for y in 0..H {
+            for x in 0..W {
+                let alive = (self.random() >> 24) < self.density as u32;
+                self.ages[y][x] = alive as u8;
+            }
+        }
+        self.history = [0; HISTORY];
+        self.generation = 0;
+        self.linger = None;
+    }
+
+    /// Start again from `seed`
+    pub fn reseed(&mut self, seed: u32) {
+        self.rng = if seed == 0 { 1 } else { seed };
+        self.randomize();
+    }
+
+    pub fn is_alive(&self, x: usize, y: usize) -> bool {
+        return self.ages[y][x] != 0;
+    }
+
+    /// Generations the cell has been alive for, 0 if it is dead
+    pub fn age(&self, x: usize, y: usize) -> u8 {
+        return self.ages[y][x];
+    }
+
+    pub fn set_alive(&mut self, x: usize, y: usize, alive: bool) {
+        self.ages[y][x] = alive as u8;
+    }
+
+    /// Generations since the last seeding
+    pub fn generation(&self) -> u32 {
+        return self.generation;
+    }
+
+    fn neighbours(&self, x: usize, y: usize) -> u32 {
+        let mut count = 0;
+        for dy in [H - 1, 0, 1] {
+            for dx in [W - 1, 0, 1] {
+                if (dx, dy) != (0, 0) && self.is_alive((x + dx) % W, (y + dy) % H) {
+                    count += 1;
+                }
+            }
+        }
+        return count;
+    }
+
+    /// FNV-1a hash of which cells are alive
+    fn hash(&self) -> u32 {
+        let mut hash = 0x811c_9dc5_u32;
+        for row in &self.ages {
+            for &age in row {
+                hash = (hash ^ (age != 0) as u32).wrapping_mul(0x0100_0193);
+            }
+        }
+        return hash;
+    }
+
+    /// Compute the next generation, and return whether the board was seeded
+    /// again instead because it had died out, stagnated or cycled
+    pub fn step(&mut self) -> bool {
+        if let Some(linger) = self.linger {
+            if linger == 0 {
+                self.randomize();
+                return true;
+            }
+            self.linger = Some(linger - 1);
+        }
+        let mut next = [[0; W]; H];
+        for (y, row) in next.iter_mut().enumerate() {
+            for (x, age) in row.iter_mut().enumerate() {
+                let alive = self.is_alive(x, y);
+                if self.rule.next(alive, self.neighbours(x, y)) {
+                    *age = self.ages[y][x].saturating_add(1);
+                }
+            }
+        }
+        self.ages = next;
+        self.generation = self.generation.wrapping_add(1);
+        let hash = self.hash();
+        let extinct = self.ages.iter().flatten().all(|&age| age == 0);
+        if extinct {
+            // Nothing left to show
+            self.randomize();
+            return true;
+        }
+        if self.linger.is_none() && self.history.contains(&hash) {
+            self.linger = Some(LINGER_GENERATIONS);
+        }
+        self.history[self.generation as usize % HISTORY] = hash;
+        return false;
+    }
+
+    /// Colour of a cell
+    pub fn color(&self, x: usize, y: usize) -> Color {
+        return match self.ages[y][x] {
+            0 => Color::BLACK,
+            age => color_at(self.stops, (age - 1).min(MAX_AGE) as f32 / MAX_AGE as f32),
+        };
+    }
+
+    /// The board with the cells coloured by age
+    pub fn render(&self) -> Image<W, H> {
+        let mut image = Image::default();
+        for ((x, y), pixel) in image.pixels_mut() {
+            *pixel = self.color(x, y);
+        }
+        return image;
+    }
+}
+
+impl<const W: usize, const H: usize> Effect<W, H> for Automaton<W, H> {
+    fn render(&mut self, t: Instant, image: &mut Image<W, H>) {
+        for _ in 0..steps(&mut self.last, t, self.step_ms) {
+            self.step();
+        }
+        *image = Automaton::render(self);
+    }
+}
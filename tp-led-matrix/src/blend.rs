@@ -0,0 +1,154 @@
+use crate::{Color, Image};
+
+/// A colour with an opacity, 0 being transparent and 255 opaque
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub fn new(color: Color, a: u8) -> Self {
+        return Rgba {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a,
+        };
+    }
+
+    pub fn color(&self) -> Color {
+        return Color {
+            r: self.r,
+            g: self.g,
+            b: self.b,
+        };
+    }
+
+    /// Composite this colour over `dst`
+    pub fn over(&self, dst: Color) -> Color {
+        return Color::lerp(dst, self.color(), self.a);
+    }
+}
+
+impl From<Color> for Rgba {
+    fn from(color: Color) -> Self {
+        return Rgba::new(color, 255);
+    }
+}
+
+/// Per-pixel opacity used when blending a whole image, with the same layout
+/// as an `Image`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mask([u8; 64]);
+
+impl Mask {
+    pub fn new_solid(alpha: u8) -> Self {
+        return Mask([alpha; 64]);
+    }
+
+    /// Take the brightest channel of every pixel as its opacity, so that
+    /// text or shapes drawn in white on black give their own mask
+    pub fn from_image(image: &Image) -> Self {
+        let mut mask = Mask::new_solid(0);
+        for row in 0..8 {
+            for (col, pixel) in image.row(row).iter().enumerate() {
+                mask.0[8 * row + col] = pixel.r.max(pixel.g).max(pixel.b);
+            }
+        }
+        return mask;
+    }
+}
+
+impl Default for Mask {
+    fn default() -> Self {
+        return Mask::new_solid(0);
+    }
+}
+
+impl core::ops::Index<(usize, usize)> for Mask {
+    type Output = u8;
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        return &self.0[8 * index.0 + index.1];
+    }
+}
+
+impl core::ops::IndexMut<(usize, usize)> for Mask {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        return &mut self.0[8 * index.0 + index.1];
+    }
+}
+
+/// How a source pixel is combined with the destination one, before opacity
+/// is applied
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// The source replaces the destination
+    Normal,
+    /// Saturating sum, brightening
+    Add,
+    /// Product, darkening; white leaves the destination unchanged
+    Multiply,
+    /// Inverse of the product of the inverses, brightening; black leaves the
+    /// destination unchanged
+    Screen,
+    /// Brightest value of every channel
+    Max,
+    /// Darkest value of every channel
+    Min,
+    /// Bitwise exclusive or of every channel
+    Xor,
+}
+
+impl Mode {
+    /// Combine `src` with `dst`, both fully opaque
+    pub fn apply(&self, dst: Color, src: Color) -> Color {
+        let channels = |f: fn(u8, u8) -> u8| Color {
+            r: f(dst.r, src.r),
+            g: f(dst.g, src.g),
+            b: f(dst.b, src.b),
+        };
+        return match self {
+            Mode::Normal => src,
+            Mode::Add => dst + src,
+            Mode::Multiply => dst * src,
+            Mode::Screen => {
+                channels(|d, s| 255 - (((255 - d) as u32 * (255 - s) as u32 + 127) / 255) as u8)
+            }
+            Mode::Max => channels(u8::max),
+            Mode::Min => channels(u8::min),
+            Mode::Xor => channels(|d, s| d ^ s),
+        };
+    }
+}
+
+impl Image {
+    /// Combine `src` into this image with `mode`
+    pub fn blend(&mut self, src: &Image, mode: Mode) {
+        self.blend_with_alpha(src, mode, 255);
+    }
+
+    /// Combine `src` into this image with `mode`, then mix the result with
+    /// the original image according to `alpha`
+    pub fn blend_with_alpha(&mut self, src: &Image, mode: Mode, alpha: u8) {
+        for row in 0..8 {
+            for col in 0..8 {
+                let dst = self[(row, col)];
+                self[(row, col)] = Color::lerp(dst, mode.apply(dst, src[(row, col)]), alpha);
+            }
+        }
+    }
+
+    /// Like `blend_with_alpha()`, with a different opacity for every pixel
+    pub fn blend_masked(&mut self, src: &Image, mode: Mode, mask: &Mask) {
+        for row in 0..8 {
+            for col in 0..8 {
+                let dst = self[(row, col)];
+                let blended = mode.apply(dst, src[(row, col)]);
+                self[(row, col)] = Color::lerp(dst, blended, mask[(row, col)]);
+            }
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use crate::transform::{Orientation, Rotation};
+use crate::Color;
+use core::str::FromStr;
+
+/// Fixed point unit of gains and matrix coefficients: 256 means 1.0
+pub const ONE: u16 = 256;
+
+/// Colour temperature giving an unchanged white, in Kelvin
+pub const NEUTRAL_TEMPERATURE: u16 = 6500;
+
+/// White point from 1000 K to 12000 K in steps of 500 K, brightest channel
+/// at `ONE`, relative to `NEUTRAL_TEMPERATURE`
+const WHITE_POINTS: [[u16; 3]; 23] = [
+    [256, 68, 0],
+    [256, 109, 0],
+    [256, 138, 14],
+    [256, 160, 72],
+    [256, 179, 113],
+    [256, 194, 144],
+    [256, 207, 170],
+    [256, 219, 192],
+    [256, 230, 211],
+    [256, 239, 228],
+    [256, 248, 242],
+    [256, 256, 256],
+    [239, 239, 256],
+    [226, 232, 256],
+    [218, 227, 256],
+    [211, 223, 256],
+    [206, 220, 256],
+    [202, 218, 256],
+    [199, 215, 256],
+    [195, 214, 256],
+    [193, 212, 256],
+    [190, 210, 256],
+    [188, 209, 256],
+];
+
+/// Red, green and blue gains of a white at `temperature` Kelvin, linearly
+/// interpolated in the table and clamped to its range
+pub fn white_point(temperature: u16) -> [u16; 3] {
+    let offset = (temperature.clamp(1000, 12000) - 1000) as u32;
+    let (index, fract) = ((offset / 500) as usize, offset % 500);
+    let low = WHITE_POINTS[index];
+    let high = WHITE_POINTS[(index + 1).min(WHITE_POINTS.len() - 1)];
+    let mut white = [0; 3];
+    for c in 0..3 {
+        white[c] = ((low[c] as u32 * (500 - fract) + high[c] as u32 * fract + 250) / 500) as u16;
+    }
+    return white;
+}
+
+/// Colour correction settings, applied before gamma correction
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Correction {
+    /// Red, green and blue gains, in units of `ONE`
+    pub gain: [u16; 3],
+    /// White point in Kelvin, lower values being warmer
+    pub temperature: u16,
+    /// Applied first: every output channel is the sum of the input channels
+    /// weighted by its row, in units of `ONE`
+    pub matrix: Option<[[i16; 3]; 3]>,
+}
+
+impl Correction {
+    pub const NEUTRAL: Correction = Correction {
+        gain: [ONE; 3],
+        temperature: NEUTRAL_TEMPERATURE,
+        matrix: None,
+    };
+}
+
+impl Default for Correction {
+    fn default() -> Self {
+        return Correction::NEUTRAL;
+    }
+}
+
+/// A `Correction` prepared to be applied to every pixel quickly
+#[derive(Copy, Clone, Debug)]
+pub struct Corrector {
+    correction: Correction,
+    /// Gain and white point together, 65536 meaning 1.0
+    scale: [u32; 3],
+}
+
+impl Corrector {
+    pub fn new(correction: &Correction) -> Self {
+        let white = white_point(correction.temperature);
+        let mut scale = [0; 3];
+        for c in 0..3 {
+            scale[c] = correction.gain[c] as u32 * white[c] as u32;
+        }
+        return Corrector {
+            correction: *correction,
+            scale,
+        };
+    }
+
+    pub fn correction(&self) -> &Correction {
+        return &self.correction;
+    }
+
+    pub fn apply(&self, color: Color) -> Color {
+        let input = [color.r as i32, color.g as i32, color.b as i32];
+        let mut output = [0u8; 3];
+        for c in 0..3 {
+            let mixed = match &self.correction.matrix {
+                Some(matrix) => {
+                    let sum: i32 = (0..3).map(|k| matrix[c][k] as i32 * input[k]).sum();
+                    ((sum + 128) >> 8).clamp(0, 255) as u32
+                }
+                None => input[c] as u32,
+            };
+            output[c] = ((mixed * self.scale[c] + 32768) >> 16).min(255) as u8;
+        }
+        return Color {
+            r: output[0],
+            g: output[1],
+            b: output[2],
+        };
+    }
+}
+
+impl Default for Corrector {
+    fn default() -> Self {
+        return Corrector::new(&Correction::NEUTRAL);
+    }
+}
+
+/// Why bytes could not be read as a `Profile`, or text as a `Setting`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    BadLength,
+    BadVersion(u8),
+    BadValue,
+    UnknownSetting,
+    /// Missing or unexpected words after the setting
+    BadArguments,
+}
+
+/// Everything known about a given panel, to be stored along with it
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    pub orientation: Orientation,
+    pub correction: Correction,
+}
+
+impl Profile {
+    /// Size of a stored profile
+    pub const LEN: usize = 30;
+    const VERSION: u8 = 1;
+
+    pub const NEUTRAL: Profile = Profile {
+        orientation: Orientation::NORMAL,
+        correction: Correction::NEUTRAL,
+    };
+
+    /// Version byte, rotation in quarter turns, mirroring, gains, temperature,
+    /// matrix presence then coefficients, multi-byte values being little-endian
+    pub fn to_bytes(&self) -> [u8; Profile::LEN] {
+        let mut bytes = [0; Profile::LEN];
+        bytes[0] = Profile::VERSION;
+        bytes[1] = match self.orientation.rotation {
+            Rotation::Deg0 => 0,
+            Rotation::Deg90 => 1,
+            Rotation::Deg180 => 2,
+            Rotation::Deg270 => 3,
+        };
+        bytes[2] = self.orientation.mirrored as u8;
+        let correction = &self.correction;
+        for (c, gain) in correction.gain.iter().enumerate() {
+            bytes[3 + 2 * c..5 + 2 * c].copy_from_slice(&gain.to_le_bytes());
+        }
+        bytes[9..11].copy_from_slice(&correction.temperature.to_le_bytes());
+        if let Some(matrix) = &correction.matrix {
+            bytes[11] = 1;
+            for (i, coefficient) in matrix.iter().flatten().enumerate() {
+                bytes[12 + 2 * i..14 + 2 * i].copy_from_slice(&coefficient.to_le_bytes());
+            }
+        }
+        return bytes;
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Profile, Error> {
+        if bytes.len() != Profile::LEN {
+            return Err(Error::BadLength);
+        }
+        if bytes[0] != Profile::VERSION {
+            return Err(Error::BadVersion(bytes[0]));
+        }
+        let word = |i: usize| [bytes[i], bytes[i + 1]];
+        let rotation = match bytes[1] {
+            0 => Rotation::Deg0,
+            1 => Rotation::Deg90,
+            2 => Rotation::Deg180,
+            3 => Rotation::Deg270,
+            _ => return Err(Error::BadValue),
+        };
+        let mirrored = match bytes[2] {
+            0 => false,
+            1 => true,
+            _ => return Err(Error::BadValue),
+        };
+        let matrix = match bytes[11] {
+            0 => None,
+            1 => {
+                let mut matrix = [[0; 3]; 3];
+                for (i, coefficient) in matrix.iter_mut().flatten().enumerate() {
+                    *coefficient = i16::from_le_bytes(word(12 + 2 * i));
+                }
+                Some(matrix)
+            }
+            _ => return Err(Error::BadValue),
+        };
+        return Ok(Profile {
+            orientation: Orientation { rotation, mirrored },
+            correction: Correction {
+                gain: [
+                    u16::from_le_bytes(word(3)),
+                    u16::from_le_bytes(word(5)),
+                    u16::from_le_bytes(word(7)),
+                ],
+                temperature: u16::from_le_bytes(word(9)),
+                matrix,
+            },
+        });
+    }
+
+    /// Change one setting, keeping the others
+    pub fn apply(&mut self, setting: &Setting) {
+        match *setting {
+            Setting::Rotation(rotation) => self.orientation.rotation = rotation,
+            Setting::Mirrored(mirrored) => self.orientation.mirrored = mirrored,
+            Setting::Gain(gain) => self.correction.gain = gain,
+            Setting::Temperature(temperature) => self.correction.temperature = temperature,
+            Setting::Matrix(matrix) => self.correction.matrix = matrix,
+            Setting::Reset => *self = Profile::NEUTRAL,
+        }
+    }
+}
+
+/// Change to a `Profile`, made at runtime from the serial line
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Setting {
+    Rotation(Rotation),
+    Mirrored(bool),
+    Gain([u16; 3]),
+    Temperature(u16),
+    Matrix(Option<[[i16; 3]; 3]>),
+    /// Back to `Profile::NEUTRAL`
+    Reset,
+}
+
+impl FromStr for Setting {
+    type Err = Error;
+
+    /// Parse `rotate 0|90|180|270`, `mirror on|off`, `gain <r> <g> <b>`,
+    /// `temperature <kelvin>`, `matrix off`, `matrix <9 coefficients>` row
+    /// by row, or `reset`. Gains and coefficients are decimal numbers such as
+    /// `0.85`, 1 leaving the channel unchanged.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (name, rest) = s.split_once(' ').unwrap_or((s, ""));
+        let setting = match (name, rest.trim()) {
+            ("rotate", "0") => Setting::Rotation(Rotation::Deg0),
+            ("rotate", "90") => Setting::Rotation(Rotation::Deg90),
+            ("rotate", "180") => Setting::Rotation(Rotation::Deg180),
+            ("rotate", "270") => Setting::Rotation(Rotation::Deg270),
+            ("mirror", "on") => Setting::Mirrored(true),
+            ("mirror", "off") => Setting::Mirrored(false),
+            ("gain", gains) => {
+                let mut gain = [0; 3];
+                for (gain, value) in gain.iter_mut().zip(parse_fixed_list::<3>(gains)?) {
+                    *gain = u16::try_from(value).map_err(|_| Error::BadValue)?;
+                }
+                Setting::Gain(gain)
+            }
+            ("temperature", kelvin) => match kelvin.parse() {
+                Ok(kelvin @ 1000..=12000) => Setting::Temperature(kelvin),
+                _ => return Err(Error::BadValue),
+            },
+            ("matrix", "off") => Setting::Matrix(None),
+            ("matrix", coefficients) => {
+                let values = parse_fixed_list::<9>(coefficients)?;
+                let mut matrix = [[0; 3]; 3];
+                for (coefficient, value) in matrix.iter_mut().flatten().zip(values) {
+                    *coefficient = i16::try_from(value).map_err(|_| Error::BadValue)?;
+                }
+                Setting::Matrix(Some(matrix))
+            }
+            ("reset", "") => Setting::Reset,
+            ("rotate" | "mirror" | "reset", _) => return Err(Error::BadArguments),
+            _ => return Err(Error::UnknownSetting),
+        };
+        return Ok(setting);
+    }
+}
+
+/// Exactly `N` decimal numbers separated by spaces, in units of `ONE`
+fn parse_fixed_list<const N: usize>(s: &str) -> Result<[i32; N], Error> {
+    let mut values = [0; N];
+    let mut words = s.split_whitespace();
+    for value in values.iter_mut() {
+        let word = words.next().ok_or(Error::BadArguments)?;
+        *value = parse_fixed(word).ok_or(Error::BadValue)?;
+    }
+    if words.next().is_some() {
+        return Err(Error::BadArguments);
+    }
+    return Ok(values);
+}
+
+/// Decimal number such as `0.85` or `-1` in units of `ONE`, rounded to the
+/// nearest unit
+fn parse_fixed(s: &str) -> Option<i32> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let (int, fract) = s.split_once('.').unwrap_or((s, ""));
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if int.is_empty() || fract.len() > 6 || !digits(int) || !digits(fract) {
+        return None;
+    }
+    let int: i64 = int.parse().ok()?;
+    let fract = match fract {
+        "" => 0,
+        digits => {
+            let scale = 10i64.pow(digits.len() as u32);
+            (digits.parse::<i64>().ok()? * ONE as i64 + scale / 2) / scale
+        }
+    };
+    let value = i32::try_from(int.checked_mul(ONE as i64)? + fract).ok()?;
+    return Some(if negative { -value } else { value });
+}
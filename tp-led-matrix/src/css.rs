@@ -0,0 +1,301 @@
+use crate::hsv::{Hsl, Hsv, HUE_STEPS};
+use crate::Color;
+use core::fmt;
+use core::str::FromStr;
+
+/// Why a string is not a colour
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    Empty,
+    /// `#` not followed by 3 or 6 hexadecimal digits
+    BadHex,
+    /// Not 3 numbers between the parentheses of `rgb()`, `hsv()` or `hsl()`
+    BadArguments,
+    /// A channel above 255 or a percentage above 100
+    OutOfRange,
+    UnknownName,
+}
+
+const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    return Color { r, g, b };
+}
+
+/// The CSS named colours, sorted by name
+pub const NAMES: [(&str, Color); 148] = [
+    ("aliceblue", rgb(240, 248, 255)),
+    ("antiquewhite", rgb(250, 235, 215)),
+    ("aqua", rgb(0, 255, 255)),
+    ("aquamarine", rgb(127, 255, 212)),
+    ("azure", rgb(240, 255, 255)),
+    ("beige", rgb(245, 245, 220)),
+    ("bisque", rgb(255, 228, 196)),
+    ("black", rgb(0, 0, 0)),
+    ("blanchedalmond", rgb(255, 235, 205)),
+    ("blue", rgb(0, 0, 255)),
+    ("blueviolet", rgb(138, 43, 226)),
+    ("brown", rgb(165, 42, 42)),
+    ("burlywood", rgb(222, 184, 135)),
+    ("cadetblue", rgb(95, 158, 160)),
+    ("chartreuse", rgb(127, 255, 0)),
+    ("chocolate", rgb(210, 105, 30)),
+    ("coral", rgb(255, 127, 80)),
+    ("cornflowerblue", rgb(100, 149, 237)),
+    ("cornsilk", rgb(255, 248, 220)),
+    ("crimson", rgb(220, 20, 60)),
+    ("cyan", rgb(0, 255, 255)),
+    ("darkblue", rgb(0, 0, 139)),
+    ("darkcyan", rgb(0, 139, 139)),
+    ("darkgoldenrod", rgb(184, 134, 11)),
+    ("darkgray", rgb(169, 169, 169)),
+    ("darkgreen", rgb(0, 100, 0)),
+    ("darkgrey", rgb(169, 169, 169)),
+    ("darkkhaki", rgb(189, 183, 107)),
+    ("darkmagenta", rgb(139, 0, 139)),
+    ("darkolivegreen", rgb(85, 107, 47)),
+    ("darkorange", rgb(255, 140, 0)),
+    ("darkorchid", rgb(153, 50, 204)),
+    ("darkred", rgb(139, 0, 0)),
+    ("darksalmon", rgb(233, 150, 122)),
+    ("darkseagreen", rgb(143, 188, 143)),
+    ("darkslateblue", rgb(72, 61, 139)),
+    ("darkslategray", rgb(47, 79, 79)),
+    ("darkslategrey", rgb(47, 79, 79)),
+    ("darkturquoise", rgb(0, 206, 209)),
+    ("darkviolet", rgb(148, 0, 211)),
+    ("deeppink", rgb(255, 20, 147)),
+    ("deepskyblue", rgb(0, 191, 255)),
+    ("dimgray", rgb(105, 105, 105)),
+    ("dimgrey", rgb(105, 105, 105)),
+    ("dodgerblue", rgb(30, 144, 255)),
+    ("firebrick", rgb(178, 34, 34)),
+    ("floralwhite", rgb(255, 250, 240)),
+    ("forestgreen", rgb(34, 139, 34)),
+    ("fuchsia", rgb(255, 0, 255)),
+    ("gainsboro", rgb(220, 220, 220)),
+    ("ghostwhite", rgb(248, 248, 255)),
+    ("gold", rgb(255, 215, 0)),
+    ("goldenrod", rgb(218, 165, 32)),
+    ("gray", rgb(128, 128, 128)),
+    ("green", rgb(0, 128, 0)),
+    ("greenyellow", rgb(173, 255, 47)),
+    ("grey", rgb(128, 128, 128)),
+    ("honeydew", rgb(240, 255, 240)),
+    ("hotpink", rgb(255, 105, 180)),
+    ("indianred", rgb(205, 92, 92)),
+    ("indigo", rgb(75, 0, 130)),
+    ("ivory", rgb(255, 255, 240)),
+    ("khaki", rgb(240, 230, 140)),
+    ("lavender", rgb(230, 230, 250)),
+    ("lavenderblush", rgb(255, 240, 245)),
+    ("lawngreen", rgb(124, 252, 0)),
+    ("lemonchiffon", rgb(255, 250, 205)),
+    ("lightblue", rgb(173, 216, 230)),
+    ("lightcoral", rgb(240, 128, 128)),
+    ("lightcyan", rgb(224, 255, 255)),
+    ("lightgoldenrodyellow", rgb(250, 250, 210)),
+    ("lightgray", rgb(211, 211, 211)),
+    ("lightgreen", rgb(144, 238, 144)),
+    ("lightgrey", rgb(211, 211, 211)),
+    ("lightpink", rgb(255, 182, 193)),
+    ("lightsalmon", rgb(255, 160, 122)),
+    ("lightseagreen", rgb(32, 178, 170)),
+    ("lightskyblue", rgb(135, 206, 250)),
+    ("lightslategray", rgb(119, 136, 153)),
+    ("lightslategrey", rgb(119, 136, 153)),
+    ("lightsteelblue", rgb(176, 196, 222)),
+    ("lightyellow", rgb(255, 255, 224)),
+    ("lime", rgb(0, 255, 0)),
+    ("limegreen", rgb(50, 205, 50)),
+    ("linen", rgb(250, 240, 230)),
+    ("magenta", rgb(255, 0, 255)),
+    ("maroon", rgb(128, 0, 0)),
+    ("mediumaquamarine", rgb(102, 205, 170)),
+    ("mediumblue", rgb(0, 0, 205)),
+    ("mediumorchid", rgb(186, 85, 211)),
+    ("mediumpurple", rgb(147, 112, 219)),
+    ("mediumseagreen", rgb(60, 179, 113)),
+    ("mediumslateblue", rgb(123, 104, 238)),
+    ("mediumspringgreen", rgb(0, 250, 154)),
+    ("mediumturquoise", rgb(72, 209, 204)),
+    ("mediumvioletred", rgb(199, 21, 133)),
+    ("midnightblue", rgb(25, 25, 112)),
+    ("mintcream", rgb(245, 255, 250)),
+    ("mistyrose", rgb(255, 228, 225)),
+    ("moccasin", rgb(255, 228, 181)),
+    ("navajowhite", rgb(255, 222, 173)),
+    ("navy", rgb(0, 0, 128)),
+    ("oldlace", rgb(253, 245, 230)),
+    ("olive", rgb(128, 128, 0)),
+    ("olivedrab", rgb(107, 142, 35)),
+    ("orange", rgb(255, 165, 0)),
+    ("orangered", rgb(255, 69, 0)),
+    ("orchid", rgb(218, 112, 214)),
+    ("palegoldenrod", rgb(238, 232, 170)),
+    ("palegreen", rgb(152, 251, 152)),
+    ("paleturquoise", rgb(175, 238, 238)),
+    ("palevioletred", rgb(219, 112, 147)),
+    ("papayawhip", rgb(255, 239, 213)),
+    ("peachpuff", rgb(255, 218, 185)),
+    ("peru", rgb(205, 133, 63)),
+    ("pink", rgb(255, 192, 203)),
+    ("plum", rgb(221, 160, 221)),
+    ("powderblue", rgb(176, 224, 230)),
+    ("purple", rgb(128, 0, 128)),
+    ("rebeccapurple", rgb(102, 51, 153)),
+    ("red", rgb(255, 0, 0)),
+    ("rosybrown", rgb(188, 143, 143)),
+    ("royalblue", rgb(65, 105, 225)),
+    ("saddlebrown", rgb(139, 69, 19)),
+    ("salmon", rgb(250, 128, 114)),
+    ("sandybrown", rgb(244, 164, 96)),
+    ("seagreen", rgb(46, 139, 87)),
+    ("seashell", rgb(255, 245, 238)),
+    ("sienna", rgb(160, 82, 45)),
+    ("silver", rgb(192, 192, 192)),
+    ("skyblue", rgb(135, 206, 235)),
+    ("slateblue", rgb(106, 90, 205)),
+    ("slategray", rgb(112, 128, 144)),
+    ("slategrey", rgb(112, 128, 144)),
+    ("snow", rgb(255, 250, 250)),
+    ("springgreen", rgb(0, 255, 127)),
+    ("steelblue", rgb(70, 130, 180)),
+    ("tan", rgb(210, 180, 140)),
+    ("teal", rgb(0, 128, 128)),
+    ("thistle", rgb(216, 191, 216)),
+    ("tomato", rgb(255, 99, 71)),
+    ("turquoise", rgb(64, 224, 208)),
+    ("violet", rgb(238, 130, 238)),
+    ("wheat", rgb(245, 222, 179)),
+    ("white", rgb(255, 255, 255)),
+    ("whitesmoke", rgb(245, 245, 245)),
+    ("yellow", rgb(255, 255, 0)),
+    ("yellowgreen", rgb(154, 205, 50)),
+];
+
+impl Color {
+    /// CSS colour called `name`, whatever its case
+    pub fn from_name(name: &str) -> Option<Color> {
+        return NAMES
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(_, color)| color);
+    }
+}
+
+/// Value of one or two hexadecimal digits, a single digit being repeated
+fn hex(digits: &str) -> Result<u8, Error> {
+    let value = u8::from_str_radix(digits, 16).map_err(|_| Error::BadHex)?;
+    if digits.len() == 1 {
+        return Ok(value * 17);
+    }
+    return Ok(value);
+}
+
+/// `value` or `value%`, scaled so that `max` or `100%` gives 255
+fn channel(arg: &str, max: u32) -> Result<u8, Error> {
+    let (digits, max) = match arg.strip_suffix('%') {
+        Some(digits) => (digits, 100),
+        None => (arg, max),
+    };
+    let value: u32 = digits.parse().map_err(|_| Error::BadArguments)?;
+    if value > max {
+        return Err(Error::OutOfRange);
+    }
+    return Ok(((value * 255 + max / 2) / max) as u8);
+}
+
+/// Angle in degrees, with an optional `deg` suffix, as a hue
+fn hue(arg: &str) -> Result<u16, Error> {
+    let digits = arg.strip_suffix("deg").unwrap_or(arg);
+    let degrees: u32 = digits.parse().map_err(|_| Error::BadArguments)?;
+    return Ok(((degrees % 360 * HUE_STEPS as u32 + 180) / 360) as u16);
+}
+
+/// The 3 arguments of `name(a, b, c)`, separated by commas or spaces
+fn arguments<'a>(s: &'a str, name: &str) -> Option<Result<[&'a str; 3], Error>> {
+    let head = s.get(..name.len())?;
+    if !head.eq_ignore_ascii_case(name) {
+        return None;
+    }
+    let inner = s[name.len()..].trim_start();
+    let Some(inner) = inner.strip_prefix('(').and_then(|s| s.strip_suffix(')')) else {
+        return Some(Err(Error::BadArguments));
+    };
+    let mut args = inner
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty());
+    let result = match (args.next(), args.next(), args.next(), args.next()) {
+        (Some(a), Some(b), Some(c), None) => Ok([a, b, c]),
+        _ => Err(Error::BadArguments),
+    };
+    return Some(result);
+}
+
+impl FromStr for Color {
+    type Err = Error;
+
+    /// Parse `#rrggbb`, `#rgb`, `rgb(r, g, b)` with channels from 0 to 255,
+    /// `hsv(h, s, v)` and `hsl(h, s, l)` with a hue in degrees and percentages
+    /// (the `%` being optional), or a CSS colour name. Case does not matter.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(Error::Empty);
+        }
+        if let Some(digits) = s.strip_prefix('#') {
+            if !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+                return Err(Error::BadHex);
+            }
+            let width = match digits.len() {
+                3 => 1,
+                6 => 2,
+                _ => return Err(Error::BadHex),
+            };
+            return Ok(Color {
+                r: hex(&digits[..width])?,
+                g: hex(&digits[width..2 * width])?,
+                b: hex(&digits[2 * width..])?,
+            });
+        }
+        if let Some(args) = arguments(s, "rgb") {
+            let [r, g, b] = args?;
+            return Ok(Color {
+                r: channel(r, 255)?,
+                g: channel(g, 255)?,
+                b: channel(b, 255)?,
+            });
+        }
+        if let Some(args) = arguments(s, "hsv") {
+            let [h, s, v] = args?;
+            return Ok(Hsv {
+                h: hue(h)?,
+                s: channel(s, 100)?,
+                v: channel(v, 100)?,
+            }
+            .into());
+        }
+        if let Some(args) = arguments(s, "hsl") {
+            let [h, s, l] = args?;
+            return Ok(Hsl {
+                h: hue(h)?,
+                s: channel(s, 100)?,
+                l: channel(l, 100)?,
+            }
+            .into());
+        }
+        return Color::from_name(s).ok_or(Error::UnknownName);
+    }
+}
+
+/// Written as `#rrggbb`
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b);
+    }
+}
+
+impl defmt::Format for Color {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "#{=u8:02x}{=u8:02x}{=u8:02x}", self.r, self.g, self.b);
+    }
+}
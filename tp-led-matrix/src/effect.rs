@@ -0,0 +1,492 @@
+use crate::hsv::HUE_STEPS;
+use crate::pattern::{color_at, Stop};
+use crate::timeline::Easing;
+use crate::{Color, Image, Instant};
+use core::f32::consts::PI;
+use fugit::ExtU32;
+use micromath::F32Ext;
+
+/// Animation drawn into an image frame after frame
+pub trait Effect<const W: usize = 8, const H: usize = 8> {
+    /// Draw into `image` what is shown at `t`. Effects with a state move on
+    /// by the time elapsed since the previous call, so `t` should not go
+    /// backwards.
+    fn render(&mut self, t: Instant, image: &mut Image<W, H>);
+}
+
+/// Steps taken at most at once by the effects moving on in steps, so that a
+/// long pause does not stall the next frame
+const MAX_STEPS: u32 = 8;
+
+/// Milliseconds from `from` to `to`, 0 if `to` comes first
+pub(crate) fn millis_between(from: Instant, to: Instant) -> u64 {
+    return to.checked_duration_since(from).map_or(0, |d| d.to_millis());
+}
+
+/// Steps of `period_ms` elapsed since `last`, which moves on by as much.
+/// The first call takes a single step.
+pub(crate) fn steps(last: &mut Option<Instant>, t: Instant, period_ms: u32) -> u32 {
+    let Some(previous) = *last else {
+        *last = Some(t);
+        return 1;
+    };
+    let n = millis_between(previous, t) / period_ms.max(1) as u64;
+    if n > MAX_STEPS as u64 {
+        *last = Some(t);
+        return MAX_STEPS;
+    }
+    let n = n as u32;
+    *last = Some(previous + (n * period_ms).millis());
+    return n;
+}
+
+/// Seconds elapsed since `last`, which becomes `t`, 0.0 at the first call
+fn elapsed(last: &mut Option<Instant>, t: Instant) -> f32 {
+    let seconds = match *last {
+        Some(previous) => millis_between(previous, t).min(1000) as f32 / 1000.0,
+        None => 0.0,
+    };
+    *last = Some(t);
+    return seconds;
+}
+
+/// Fraction of the current period, from 0.0 to 1.0
+fn phase(t: Instant, period_ms: u32) -> f32 {
+    let period_ms = period_ms.max(1);
+    return (t.ticks() % period_ms as u64) as f32 / period_ms as f32;
+}
+
+/// Xorshift generator, so that an effect always goes the same way for a
+/// given seed
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Rng(u32);
+
+impl Rng {
+    pub(crate) const fn new(seed: u32) -> Self {
+        // xorshift never leaves 0
+        return Rng(if seed == 0 { 1 } else { seed });
+    }
+
+    pub(crate) fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        return self.0;
+    }
+
+    /// Between 0 and `n` excluded
+    pub(crate) fn below(&mut self, n: u32) -> u32 {
+        return ((self.next() as u64 * n as u64) >> 32) as u32;
+    }
+
+    /// Between 0.0 and 1.0 excluded
+    fn unit(&mut self) -> f32 {
+        return (self.next() >> 8) as f32 / (1 << 24) as f32;
+    }
+}
+
+const PLASMA: [Stop; 5] = [
+    Stop::new(0.0, Color::BLUE),
+    Stop::new(0.25, Color::PURPLE),
+    Stop::new(0.5, Color::RED),
+    Stop::new(0.75, Color::ORANGE),
+    Stop::new(1.0, Color::YELLOW),
+];
+
+/// Waves of colour drifting over each other
+#[derive(Copy, Clone, Debug)]
+pub struct Plasma<'a> {
+    pub stops: &'a [Stop],
+    /// Time after which the plasma is back where it started
+    pub period_ms: u32,
+    /// Size of the waves, in pixels per radian
+    pub scale: f32,
+}
+
+impl<'a> Plasma<'a> {
+    pub const fn new() -> Self {
+        return Plasma {
+            stops: &PLASMA,
+            period_ms: 8000,
+            scale: 2.0,
+        };
+    }
+}
+
+impl Default for Plasma<'_> {
+    fn default() -> Self {
+        return Plasma::new();
+    }
+}
+
+impl<const W: usize, const H: usize> Effect<W, H> for Plasma<'_> {
+    fn render(&mut self, t: Instant, image: &mut Image<W, H>) {
+        // Every wave moves a whole number of turns per period
+        let p = phase(t, self.period_ms) * 2.0 * PI;
+        let (cos, sin) = (p.cos(), p.sin());
+        let (cx, cy) = ((W as f32 - 1.0) / 2.0, (H as f32 - 1.0) / 2.0);
+        for ((x, y), pixel) in image.pixels_mut() {
+            let (px, py) = (x as f32 / self.scale, y as f32 / self.scale);
+            let ring = (x as f32 - cx).hypot(y as f32 - cy) / self.scale;
+            let value = (px + p).sin()
+                + (py - 2.0 * p).sin()
+                + (px * cos + py * sin).sin()
+                + (ring - 3.0 * p).sin();
+            *pixel = color_at(self.stops, (value + 4.0) / 8.0);
+        }
+    }
+}
+
+const FIRE: [Stop; 5] = [
+    Stop::new(0.0, Color::BLACK),
+    Stop::new(0.35, Color::RED),
+    Stop::new(0.65, Color::ORANGE),
+    Stop::new(0.85, Color::YELLOW),
+    Stop::new(1.0, Color::WHITE),
+];
+
+/// Flames rising from the bottom row, which is fed with random heat
+#[derive(Clone, Debug)]
+pub struct Fire<const W: usize = 8, const H: usize = 8> {
+    /// Colours by heat, 0.0 being cold and 1.0 the hottest
+    pub stops: &'static [Stop],
+    /// Most heat lost going up one row, out of 255
+    pub cooling: u8,
+    pub step_ms: u32,
+    heat: [[u8; W]; H],
+    rng: Rng,
+    last: Option<Instant>,
+}
+
+impl<const W: usize, const H: usize> Fire<W, H> {
+    pub const fn new(seed: u32) -> Self {
+        return Fire {
+            stops: &FIRE,
+            cooling: (2 * 255 / H) as u8,
+            step_ms: 50,
+            heat: [[0; W]; H],
+            rng: Rng::new(seed),
+            last: None,
+        };
+    }
+
+    fn step(&mut self) {
+        for y in 0..H - 1 {
+            for x in 0..W {
+                // Heat from the row below, drifting sideways
+                let below = &self.heat[y + 1];
+                let sum =
+                    below[(x + W - 1) % W] as u32 + 2 * below[x] as u32 + below[(x + 1) % W] as u32;
+                let cooling = self.rng.below(self.cooling as u32 + 1);
+                self.heat[y][x] = (sum / 4).saturating_sub(cooling) as u8;
+            }
+        }
+        for x in 0..W {
+            self.heat[H - 1][x] = 160 + self.rng.below(96) as u8;
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Effect<W, H> for Fire<W, H> {
+    fn render(&mut self, t: Instant, image: &mut Image<W, H>) {
+        for _ in 0..steps(&mut self.last, t, self.step_ms) {
+            self.step();
+        }
+        for ((x, y), pixel) in image.pixels_mut() {
+            *pixel = color_at(self.stops, self.heat[y][x] as f32 / 255.0);
+        }
+    }
+}
+
+/// Stars closer than this are sent back to the far end
+const NEAREST_STAR: f32 = 0.05;
+
+#[derive(Copy, Clone, Debug)]
+struct Star {
+    x: f32,
+    y: f32,
+    /// Distance from 1.0, the farthest, to 0.0, the viewer
+    z: f32,
+}
+
+/// `N` stars flying towards the viewer, brighter as they get closer
+#[derive(Clone, Debug)]
+pub struct Starfield<const N: usize = 24> {
+    pub color: Color,
+    /// Distance covered every second, the whole depth being 1.0
+    pub speed: f32,
+    stars: [Star; N],
+    rng: Rng,
+    last: Option<Instant>,
+}
+
+impl<const N: usize> Starfield<N> {
+    pub const fn new(seed: u32) -> Self {
+        return Starfield {
+            color: Color::WHITE,
+            speed: 0.5,
+            stars: [Star {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            }; N],
+            rng: Rng::new(seed),
+            last: None,
+        };
+    }
+
+    /// A star somewhere in front of the viewer, at `z`
+    fn spawn(&mut self, z: f32) -> Star {
+        let x = self.rng.unit() * 2.0 - 1.0;
+        let y = self.rng.unit() * 2.0 - 1.0;
+        return Star { x, y, z };
+    }
+}
+
+impl<const N: usize, const W: usize, const H: usize> Effect<W, H> for Starfield<N> {
+    fn render(&mut self, t: Instant, image: &mut Image<W, H>) {
+        if self.last.is_none() {
+            // Spread over the whole depth to start with
+            for i in 0..N {
+                let z = NEAREST_STAR + self.rng.unit() * (1.0 - NEAREST_STAR);
+                self.stars[i] = self.spawn(z);
+            }
+        }
+        let distance = elapsed(&mut self.last, t) * self.speed;
+        *image = Image::default();
+        let (cx, cy) = (W as f32 / 2.0, H as f32 / 2.0);
+        for i in 0..N {
+            let mut star = self.stars[i];
+            star.z -= distance;
+            let (sx, sy) = (cx + star.x / star.z * cx, cy + star.y / star.z * cy);
+            let visible = sx >= 0.0 && sx < W as f32 && sy >= 0.0 && sy < H as f32;
+            if star.z < NEAREST_STAR || !visible {
+                self.stars[i] = self.spawn(1.0);
+                continue;
+            }
+            self.stars[i] = star;
+            let pixel = &mut image[(sy as usize, sx as usize)];
+            let brightness = ((1.0 - star.z) * 255.0) as u8;
+            // The nearest of the stars falling on the same pixel
+            if brightness > pixel.r.max(pixel.g).max(pixel.b) {
+                *pixel = self.color.scale(brightness);
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Drop {
+    /// Row of the head, negative while it has not appeared yet
+    head: f32,
+    /// Rows every second
+    speed: f32,
+}
+
+/// Columns of falling trails, as in the film The Matrix
+#[derive(Clone, Debug)]
+pub struct Rain<const W: usize = 8, const H: usize = 8> {
+    pub color: Color,
+    pub head_color: Color,
+    /// Pixels behind the head, fading out
+    pub trail: usize,
+    /// Slowest and fastest drops, in rows every second
+    pub speeds: (f32, f32),
+    drops: [Drop; W],
+    rng: Rng,
+    last: Option<Instant>,
+}
+
+impl<const W: usize, const H: usize> Rain<W, H> {
+    pub const fn new(seed: u32) -> Self {
+        return Rain {
+            color: Color::GREEN,
+            head_color: Color {
+                r: 180,
+                g: 255,
+                b: 180,
+            },
+            trail: 4,
+            speeds: (6.0, 14.0),
+            drops: [Drop {
+                head: 0.0,
+                speed: 0.0,
+            }; W],
+            rng: Rng::new(seed),
+            last: None,
+        };
+    }
+
+    /// A drop above the image, which appears after a random delay
+    fn spawn(&mut self) -> Drop {
+        let (slowest, fastest) = self.speeds;
+        return Drop {
+            head: -(self.rng.below(H as u32) as f32) - 1.0,
+            speed: slowest + self.rng.unit() * (fastest - slowest),
+        };
+    }
+}
+
+impl<const W: usize, const H: usize> Effect<W, H> for Rain<W, H> {
+    fn render(&mut self, t: Instant, image: &mut Image<W, H>) {
+        if self.last.is_none() {
+            for x in 0..W {
+                self.drops[x] = self.spawn();
+            }
+        }
+        let seconds = elapsed(&mut self.last, t);
+        *image = Image::default();
+        for x in 0..W {
+            let mut drop = self.drops[x];
+            drop.head += drop.speed * seconds;
+            if drop.head - self.trail as f32 >= H as f32 {
+                drop = self.spawn();
+            }
+            self.drops[x] = drop;
+            let head = drop.head.floor() as i32;
+            for k in 0..=self.trail {
+                let y = head - k as i32;
+                if y < 0 || y >= H as i32 {
+                    continue;
+                }
+                image[(y as usize, x)] = if k == 0 {
+                    self.head_color
+                } else {
+                    let fade = k * 255 / (self.trail + 1);
+                    Color::lerp(self.color, Color::BLACK, fade as u8)
+                };
+            }
+        }
+    }
+}
+
+/// Pixels lighting up at random places then fading out
+#[derive(Clone, Debug)]
+pub struct Sparkle<const W: usize = 8, const H: usize = 8> {
+    /// Colour of the sparkles, or random hues if `None`
+    pub color: Option<Color>,
+    /// Sparkles appearing every second
+    pub rate: u32,
+    /// Time a sparkle takes to fade out
+    pub fade_ms: u32,
+    colors: [[Color; W]; H],
+    levels: [[u8; W]; H],
+    /// Thousandths of a sparkle waiting to appear
+    pending: u32,
+    rng: Rng,
+    last: Option<Instant>,
+}
+
+/// Time between two updates of the sparkles
+const SPARKLE_STEP_MS: u32 = 20;
+
+impl<const W: usize, const H: usize> Sparkle<W, H> {
+    pub const fn new(seed: u32) -> Self {
+        return Sparkle {
+            color: None,
+            rate: 20,
+            fade_ms: 600,
+            colors: [[Color::BLACK; W]; H],
+            levels: [[0; W]; H],
+            pending: 0,
+            rng: Rng::new(seed),
+            last: None,
+        };
+    }
+
+    fn step(&mut self) {
+        let fade = (255 * SPARKLE_STEP_MS / self.fade_ms.max(1)).clamp(1, 255) as u8;
+        for level in self.levels.iter_mut().flatten() {
+            *level = level.saturating_sub(fade);
+        }
+        self.pending += self.rate * SPARKLE_STEP_MS;
+        while self.pending >= 1000 {
+            self.pending -= 1000;
+            let x = self.rng.below(W as u32) as usize;
+            let y = self.rng.below(H as u32) as usize;
+            self.colors[y][x] = match self.color {
+                Some(color) => color,
+                None => Color::from_hue(self.rng.below(HUE_STEPS as u32) as u16),
+            };
+            self.levels[y][x] = 255;
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Effect<W, H> for Sparkle<W, H> {
+    fn render(&mut self, t: Instant, image: &mut Image<W, H>) {
+        for _ in 0..steps(&mut self.last, t, SPARKLE_STEP_MS) {
+            self.step();
+        }
+        for ((x, y), pixel) in image.pixels_mut() {
+            *pixel = self.colors[y][x].scale(self.levels[y][x]);
+        }
+    }
+}
+
+/// The whole image slowly brightening and dimming
+#[derive(Copy, Clone, Debug)]
+pub struct Breathing {
+    pub color: Color,
+    /// Time of a whole breath
+    pub period_ms: u32,
+    /// How the light rises and falls during each half of a breath
+    pub easing: Easing,
+}
+
+impl Breathing {
+    pub const fn new(color: Color) -> Self {
+        return Breathing {
+            color,
+            period_ms: 4000,
+            easing: Easing::Sine,
+        };
+    }
+}
+
+impl<const W: usize, const H: usize> Effect<W, H> for Breathing {
+    fn render(&mut self, t: Instant, image: &mut Image<W, H>) {
+        let phase = phase(t, self.period_ms);
+        let level = self.easing.apply(1.0 - (2.0 * phase - 1.0).abs());
+        // Squared, so that the panel stays dim a little longer
+        let color = self.color.scale((level * level * 255.0) as u8);
+        for (_, pixel) in image.pixels_mut() {
+            *pixel = color;
+        }
+    }
+}
+
+/// Rainbow going diagonally across the image
+#[derive(Copy, Clone, Debug)]
+pub struct ColorWash {
+    /// Time for the hue to go round the colour wheel
+    pub period_ms: u32,
+    /// Hue difference between neighbouring pixels, out of `HUE_STEPS`
+    pub spread: u16,
+}
+
+impl ColorWash {
+    pub const fn new() -> Self {
+        return ColorWash {
+            period_ms: 6000,
+            spread: 48,
+        };
+    }
+}
+
+impl Default for ColorWash {
+    fn default() -> Self {
+        return ColorWash::new();
+    }
+}
+
+impl<const W: usize, const H: usize> Effect<W, H> for ColorWash {
+    fn render(&mut self, t: Instant, image: &mut Image<W, H>) {
+        let steps = HUE_STEPS as u32;
+        let base = (phase(t, self.period_ms) * steps as f32) as u32;
+        for ((x, y), pixel) in image.pixels_mut() {
+            let hue = base + (x + y) as u32 * self.spread as u32;
+            *pixel = Color::from_hue((hue % steps) as u16);
+        }
+    }
+}
@@ -12,13 +12,19 @@ impl From<Rgb888> for Color {
     }
 }
 
-impl OriginDimensions for Image {
+impl From<Color> for Rgb888 {
+    fn from(color: Color) -> Self {
+        Rgb888::new(color.r, color.g, color.b)
+    }
+}
+
+impl<const W: usize, const H: usize> OriginDimensions for Image<W, H> {
     fn size(&self) -> Size {
-        Size::new(8, 8)
+        Size::new(W as u32, H as u32)
     }
 }
 
-impl DrawTarget for Image {
+impl<const W: usize, const H: usize> DrawTarget for Image<W, H> {
     type Color = Rgb888;
     type Error = Infallible;
 
@@ -27,9 +33,10 @@ impl DrawTarget for Image {
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
         for Pixel(coord, color) in pixels.into_iter() {
-            if coord.x >= 0 && coord.x < 8 && coord.y >= 0 && coord.y < 8 {
-                let index: u32 = (coord.x + coord.y * 8) as u32;
-                self[(0, index as usize)] = color.into();
+            if let (Ok(x), Ok(y)) = (usize::try_from(coord.x), usize::try_from(coord.y)) {
+                if let Some(pixel) = self.get_mut(x, y) {
+                    *pixel = color.into();
+                }
             }
         }
         return Ok(());
@@ -0,0 +1,651 @@
+use embedded_graphics::{
+    mono_font::{MonoFont, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        renderer::{CharacterStyle, TextMetrics, TextRenderer},
+        Baseline, Text,
+    },
+};
+
+/// Glyphs of consecutive characters, stored as columns, bit 0 being the top
+/// row, one after the other in `columns`: the glyph of character `first + i`
+/// is `columns[offsets[i]..offsets[i + 1]]`, so that every glyph has its own
+/// width.
+#[derive(Copy, Clone, Debug)]
+pub struct Glyphs<'a> {
+    pub first: char,
+    pub offsets: &'a [u16],
+    pub columns: &'a [u8],
+}
+
+impl<'a> Glyphs<'a> {
+    pub fn get(&self, c: char) -> Option<&'a [u8]> {
+        let index = (c as u32).checked_sub(self.first as u32)? as usize;
+        let start = *self.offsets.get(index)?;
+        let end = *self.offsets.get(index.checked_add(1)?)?;
+        return self.columns.get(start as usize..end as usize);
+    }
+}
+
+/// Proportional bitmap font of at most 8 rows, made of several tables of
+/// glyphs so that the characters it covers need not be consecutive
+#[derive(Copy, Clone, Debug)]
+pub struct Font<'a> {
+    pub height: u8,
+    /// Row on which the characters without descender stand
+    pub baseline: u8,
+    /// Empty columns between two glyphs
+    pub spacing: u8,
+    pub tables: &'a [Glyphs<'a>],
+    /// Adjustments of the spacing between pairs of characters
+    pub kerning: &'a [(char, char, i8)],
+}
+
+impl<'a> Font<'a> {
+    /// Columns of the glyph of `c`, if the font has one
+    pub fn get(&self, c: char) -> Option<&'a [u8]> {
+        return self.tables.iter().find_map(|table| table.get(c));
+    }
+
+    /// Columns of the glyph of `c`. If the font lacks it, the glyph of
+    /// `substitute(c)` is used instead, then the replacement character, then
+    /// `?`, then nothing.
+    pub fn glyph(&self, c: char) -> &'a [u8] {
+        return self
+            .get(c)
+            .or_else(|| substitute(c).and_then(|c| self.get(c)))
+            .or_else(|| self.get(char::REPLACEMENT_CHARACTER))
+            .or_else(|| self.get('?'))
+            .unwrap_or(&[]);
+    }
+
+    /// Whether `text` can be drawn without any replacement character
+    pub fn covers(&self, text: &str) -> bool {
+        return text.chars().all(|c| {
+            self.get(c)
+                .or_else(|| substitute(c).and_then(|c| self.get(c)))
+                .is_some()
+        });
+    }
+
+    /// Space between `left` and `right`, kerning included
+    pub fn spacing(&self, left: char, right: char) -> i32 {
+        let kerning = self
+            .kerning
+            .iter()
+            .find(|&&(l, r, _)| l == left && r == right)
+            .map_or(0, |&(_, _, kerning)| kerning);
+        return self.spacing as i32 + kerning as i32;
+    }
+
+    /// Width of `text` in pixels, from the first column of the first glyph to
+    /// the last column of the last one
+    pub fn text_width(&self, text: &str) -> u32 {
+        let mut width = 0;
+        let mut previous = None;
+        for c in text.chars() {
+            if let Some(previous) = previous {
+                width += self.spacing(previous, c);
+            }
+            width += self.glyph(c).len() as i32;
+            previous = Some(c);
+        }
+        return width.max(0) as u32;
+    }
+}
+
+/// Text style drawing with a `Font`, to be used with `embedded_graphics::text::Text`
+#[derive(Copy, Clone, Debug)]
+pub struct FontStyle<'a> {
+    pub font: &'a Font<'a>,
+    pub text_color: Option<Rgb888>,
+    pub background_color: Option<Rgb888>,
+}
+
+impl<'a> FontStyle<'a> {
+    pub const fn new(font: &'a Font<'a>, text_color: Rgb888) -> Self {
+        return FontStyle {
+            font,
+            text_color: Some(text_color),
+            background_color: None,
+        };
+    }
+
+    /// Row of the top of the glyphs for text drawn at `y` with `baseline`
+    fn top(&self, y: i32, baseline: Baseline) -> i32 {
+        let height = self.font.height as i32;
+        return y - match baseline {
+            Baseline::Top => 0,
+            Baseline::Bottom => height - 1,
+            Baseline::Middle => (height - 1) / 2,
+            Baseline::Alphabetic => self.font.baseline as i32,
+        };
+    }
+
+    fn fill_background<D>(&self, area: Rectangle, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb888>,
+    {
+        if let Some(color) = self.background_color {
+            target.fill_solid(&area, color)?;
+        }
+        return Ok(());
+    }
+}
+
+impl CharacterStyle for FontStyle<'_> {
+    type Color = Rgb888;
+
+    fn set_text_color(&mut self, text_color: Option<Self::Color>) {
+        self.text_color = text_color;
+    }
+
+    fn set_background_color(&mut self, background_color: Option<Self::Color>) {
+        self.background_color = background_color;
+    }
+}
+
+impl TextRenderer for FontStyle<'_> {
+    type Color = Rgb888;
+
+    fn draw_string<D>(
+        &self,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let top = self.top(position.y, baseline);
+        let height = self.font.height as u32;
+        let mut x = position.x;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            let glyph = self.font.glyph(c);
+            let area = Rectangle::new(Point::new(x, top), Size::new(glyph.len() as u32, height));
+            self.fill_background(area, target)?;
+            if let Some(color) = self.text_color {
+                let pixels = glyph.iter().enumerate().flat_map(|(col, &bits)| {
+                    (0..height as i32)
+                        .filter(move |&row| bits & (1 << row) != 0)
+                        .map(move |row| Pixel(Point::new(x + col as i32, top + row), color))
+                });
+                target.draw_iter(pixels)?;
+            }
+            x += glyph.len() as i32;
+            // Like monospaced fonts, the spacing after the last glyph is part
+            // of the advance but not of the bounding box
+            let spacing = match chars.peek() {
+                Some(&next) => self.font.spacing(c, next),
+                None => self.font.spacing as i32,
+            };
+            let gap = Rectangle::new(Point::new(x, top), Size::new(spacing.max(0) as u32, height));
+            self.fill_background(gap, target)?;
+            x += spacing;
+        }
+        return Ok(Point::new(x, position.y));
+    }
+
+    fn draw_whitespace<D>(
+        &self,
+        width: u32,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let top = self.top(position.y, baseline);
+        let area = Rectangle::new(
+            Point::new(position.x, top),
+            Size::new(width, self.font.height as u32),
+        );
+        self.fill_background(area, target)?;
+        return Ok(position + Point::new(width as i32, 0));
+    }
+
+    fn measure_string(&self, text: &str, position: Point, baseline: Baseline) -> TextMetrics {
+        let width = self.font.text_width(text);
+        let advance = if text.is_empty() {
+            0
+        } else {
+            width as i32 + self.font.spacing as i32
+        };
+        let top_left = Point::new(position.x, self.top(position.y, baseline));
+        return TextMetrics {
+            bounding_box: Rectangle::new(top_left, Size::new(width, self.font.height as u32)),
+            next_position: position + Point::new(advance, 0),
+        };
+    }
+
+    fn line_height(&self) -> u32 {
+        return self.font.height as u32;
+    }
+}
+
+/// Either kind of font, for code which accepts both
+#[derive(Copy, Clone, Debug)]
+pub enum Typeface<'a> {
+    Mono(&'a MonoFont<'a>),
+    Proportional(&'a Font<'a>),
+}
+
+impl Typeface<'_> {
+    /// Bounding box of `text` drawn with its top left corner at the origin,
+    /// and where the text following it would start
+    pub fn measure(&self, text: &str) -> TextMetrics {
+        let (origin, color) = (Point::zero(), Rgb888::WHITE);
+        return match *self {
+            Typeface::Mono(font) => {
+                MonoTextStyle::new(font, color).measure_string(text, origin, Baseline::Top)
+            }
+            Typeface::Proportional(font) => {
+                FontStyle::new(font, color).measure_string(text, origin, Baseline::Top)
+            }
+        };
+    }
+
+    /// Size of `text` drawn with its top left corner at the origin
+    pub fn text_size(&self, text: &str) -> Size {
+        return self.measure(text).bounding_box.size;
+    }
+
+    /// Draw `text` in `color` with its top left corner at `position`
+    pub fn draw<D>(
+        &self,
+        text: &str,
+        position: Point,
+        color: Rgb888,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb888>,
+    {
+        self.draw_styled(text, position, Some(color), None, target)?;
+        return Ok(());
+    }
+
+    /// Draw `text` with its top left corner at `position`, leaving out the
+    /// glyphs or the background if their colour is `None`, and return where
+    /// the text following it would start
+    pub fn draw_styled<D>(
+        &self,
+        text: &str,
+        position: Point,
+        text_color: Option<Rgb888>,
+        background_color: Option<Rgb888>,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Rgb888>,
+    {
+        return match *self {
+            Typeface::Mono(font) => {
+                let mut style = MonoTextStyle::new(font, Rgb888::WHITE);
+                style.set_text_color(text_color);
+                style.set_background_color(background_color);
+                Text::with_baseline(text, position, style, Baseline::Top).draw(target)
+            }
+            Typeface::Proportional(font) => {
+                let style = FontStyle {
+                    font,
+                    text_color,
+                    background_color,
+                };
+                Text::with_baseline(text, position, style, Baseline::Top).draw(target)
+            }
+        };
+    }
+}
+
+/// Icons, in the private use area of Unicode, drawn by the bundled fonts.
+/// Common emoji and symbols standing for them are drawn as them too.
+pub const ICON_HEART: char = '\u{e000}';
+pub const ICON_STAR: char = '\u{e001}';
+pub const ICON_SMILEY: char = '\u{e002}';
+pub const ICON_NOTE: char = '\u{e003}';
+pub const ICON_SUN: char = '\u{e004}';
+pub const ICON_CHECK: char = '\u{e005}';
+pub const ICON_CROSS: char = '\u{e006}';
+pub const ICON_WARNING: char = '\u{e007}';
+pub const ICON_ARROW_UP: char = '\u{e008}';
+pub const ICON_ARROW_DOWN: char = '\u{e009}';
+pub const ICON_ARROW_LEFT: char = '\u{e00a}';
+pub const ICON_ARROW_RIGHT: char = '\u{e00b}';
+
+/// Closest character to `c` which fonts are more likely to have: the ASCII
+/// letter without its accent, an ASCII look-alike of a typographic sign, or
+/// the icon of an emoji
+pub fn substitute(c: char) -> Option<char> {
+    let index = SUBSTITUTES.binary_search_by_key(&c, |&(c, _)| c).ok()?;
+    return Some(SUBSTITUTES[index].1);
+}
+
+/// Sorted by the first character
+const SUBSTITUTES: [(char, char); 320] = [
+    ('\u{a0}', ' '),
+    ('\u{ab}', '"'),
+    ('\u{bb}', '"'),
+    ('\u{c0}', 'A'),
+    ('\u{c1}', 'A'),
+    ('\u{c2}', 'A'),
+    ('\u{c3}', 'A'),
+    ('\u{c4}', 'A'),
+    ('\u{c5}', 'A'),
+    ('\u{c6}', 'A'),
+    ('\u{c7}', 'C'),
+    ('\u{c8}', 'E'),
+    ('\u{c9}', 'E'),
+    ('\u{ca}', 'E'),
+    ('\u{cb}', 'E'),
+    ('\u{cc}', 'I'),
+    ('\u{cd}', 'I'),
+    ('\u{ce}', 'I'),
+    ('\u{cf}', 'I'),
+    ('\u{d0}', 'D'),
+    ('\u{d1}', 'N'),
+    ('\u{d2}', 'O'),
+    ('\u{d3}', 'O'),
+    ('\u{d4}', 'O'),
+    ('\u{d5}', 'O'),
+    ('\u{d6}', 'O'),
+    ('\u{d7}', 'x'),
+    ('\u{d8}', 'O'),
+    ('\u{d9}', 'U'),
+    ('\u{da}', 'U'),
+    ('\u{db}', 'U'),
+    ('\u{dc}', 'U'),
+    ('\u{dd}', 'Y'),
+    ('\u{de}', 'P'),
+    ('\u{df}', 's'),
+    ('\u{e0}', 'a'),
+    ('\u{e1}', 'a'),
+    ('\u{e2}', 'a'),
+    ('\u{e3}', 'a'),
+    ('\u{e4}', 'a'),
+    ('\u{e5}', 'a'),
+    ('\u{e6}', 'a'),
+    ('\u{e7}', 'c'),
+    ('\u{e8}', 'e'),
+    ('\u{e9}', 'e'),
+    ('\u{ea}', 'e'),
+    ('\u{eb}', 'e'),
+    ('\u{ec}', 'i'),
+    ('\u{ed}', 'i'),
+    ('\u{ee}', 'i'),
+    ('\u{ef}', 'i'),
+    ('\u{f0}', 'd'),
+    ('\u{f1}', 'n'),
+    ('\u{f2}', 'o'),
+    ('\u{f3}', 'o'),
+    ('\u{f4}', 'o'),
+    ('\u{f5}', 'o'),
+    ('\u{f6}', 'o'),
+    ('\u{f8}', 'o'),
+    ('\u{f9}', 'u'),
+    ('\u{fa}', 'u'),
+    ('\u{fb}', 'u'),
+    ('\u{fc}', 'u'),
+    ('\u{fd}', 'y'),
+    ('\u{fe}', 'p'),
+    ('\u{ff}', 'y'),
+    ('\u{100}', 'A'),
+    ('\u{101}', 'a'),
+    ('\u{102}', 'A'),
+    ('\u{103}', 'a'),
+    ('\u{104}', 'A'),
+    ('\u{105}', 'a'),
+    ('\u{106}', 'C'),
+    ('\u{107}', 'c'),
+    ('\u{108}', 'C'),
+    ('\u{109}', 'c'),
+    ('\u{10a}', 'C'),
+    ('\u{10b}', 'c'),
+    ('\u{10c}', 'C'),
+    ('\u{10d}', 'c'),
+    ('\u{10e}', 'D'),
+    ('\u{10f}', 'd'),
+    ('\u{110}', 'D'),
+    ('\u{111}', 'd'),
+    ('\u{112}', 'E'),
+    ('\u{113}', 'e'),
+    ('\u{114}', 'E'),
+    ('\u{115}', 'e'),
+    ('\u{116}', 'E'),
+    ('\u{117}', 'e'),
+    ('\u{118}', 'E'),
+    ('\u{119}', 'e'),
+    ('\u{11a}', 'E'),
+    ('\u{11b}', 'e'),
+    ('\u{11c}', 'G'),
+    ('\u{11d}', 'g'),
+    ('\u{11e}', 'G'),
+    ('\u{11f}', 'g'),
+    ('\u{120}', 'G'),
+    ('\u{121}', 'g'),
+    ('\u{122}', 'G'),
+    ('\u{123}', 'g'),
+    ('\u{124}', 'H'),
+    ('\u{125}', 'h'),
+    ('\u{126}', 'H'),
+    ('\u{127}', 'h'),
+    ('\u{128}', 'I'),
+    ('\u{129}', 'i'),
+    ('\u{12a}', 'I'),
+    ('\u{12b}', 'i'),
+    ('\u{12c}', 'I'),
+    ('\u{12d}', 'i'),
+    ('\u{12e}', 'I'),
+    ('\u{12f}', 'i'),
+    ('\u{130}', 'I'),
+    ('\u{131}', 'i'),
+    ('\u{134}', 'J'),
+    ('\u{135}', 'j'),
+    ('\u{136}', 'K'),
+    ('\u{137}', 'k'),
+    ('\u{139}', 'L'),
+    ('\u{13a}', 'l'),
+    ('\u{13b}', 'L'),
+    ('\u{13c}', 'l'),
+    ('\u{13d}', 'L'),
+    ('\u{13e}', 'l'),
+    ('\u{141}', 'L'),
+    ('\u{142}', 'l'),
+    ('\u{143}', 'N'),
+    ('\u{144}', 'n'),
+    ('\u{145}', 'N'),
+    ('\u{146}', 'n'),
+    ('\u{147}', 'N'),
+    ('\u{148}', 'n'),
+    ('\u{14c}', 'O'),
+    ('\u{14d}', 'o'),
+    ('\u{14e}', 'O'),
+    ('\u{14f}', 'o'),
+    ('\u{150}', 'O'),
+    ('\u{151}', 'o'),
+    ('\u{152}', 'O'),
+    ('\u{153}', 'o'),
+    ('\u{154}', 'R'),
+    ('\u{155}', 'r'),
+    ('\u{156}', 'R'),
+    ('\u{157}', 'r'),
+    ('\u{158}', 'R'),
+    ('\u{159}', 'r'),
+    ('\u{15a}', 'S'),
+    ('\u{15b}', 's'),
+    ('\u{15c}', 'S'),
+    ('\u{15d}', 's'),
+    ('\u{15e}', 'S'),
+    ('\u{15f}', 's'),
+    ('\u{160}', 'S'),
+    ('\u{161}', 's'),
+    ('\u{162}', 'T'),
+    ('\u{163}', 't'),
+    ('\u{164}', 'T'),
+    ('\u{165}', 't'),
+    ('\u{166}', 'T'),
+    ('\u{167}', 't'),
+    ('\u{168}', 'U'),
+    ('\u{169}', 'u'),
+    ('\u{16a}', 'U'),
+    ('\u{16b}', 'u'),
+    ('\u{16c}', 'U'),
+    ('\u{16d}', 'u'),
+    ('\u{16e}', 'U'),
+    ('\u{16f}', 'u'),
+    ('\u{170}', 'U'),
+    ('\u{171}', 'u'),
+    ('\u{172}', 'U'),
+    ('\u{173}', 'u'),
+    ('\u{174}', 'W'),
+    ('\u{175}', 'w'),
+    ('\u{176}', 'Y'),
+    ('\u{177}', 'y'),
+    ('\u{178}', 'Y'),
+    ('\u{179}', 'Z'),
+    ('\u{17a}', 'z'),
+    ('\u{17b}', 'Z'),
+    ('\u{17c}', 'z'),
+    ('\u{17d}', 'Z'),
+    ('\u{17e}', 'z'),
+    ('\u{1a0}', 'O'),
+    ('\u{1a1}', 'o'),
+    ('\u{1af}', 'U'),
+    ('\u{1b0}', 'u'),
+    ('\u{1cd}', 'A'),
+    ('\u{1ce}', 'a'),
+    ('\u{1cf}', 'I'),
+    ('\u{1d0}', 'i'),
+    ('\u{1d1}', 'O'),
+    ('\u{1d2}', 'o'),
+    ('\u{1d3}', 'U'),
+    ('\u{1d4}', 'u'),
+    ('\u{1d5}', 'U'),
+    ('\u{1d6}', 'u'),
+    ('\u{1d7}', 'U'),
+    ('\u{1d8}', 'u'),
+    ('\u{1d9}', 'U'),
+    ('\u{1da}', 'u'),
+    ('\u{1db}', 'U'),
+    ('\u{1dc}', 'u'),
+    ('\u{1de}', 'A'),
+    ('\u{1df}', 'a'),
+    ('\u{1e0}', 'A'),
+    ('\u{1e1}', 'a'),
+    ('\u{1e6}', 'G'),
+    ('\u{1e7}', 'g'),
+    ('\u{1e8}', 'K'),
+    ('\u{1e9}', 'k'),
+    ('\u{1ea}', 'O'),
+    ('\u{1eb}', 'o'),
+    ('\u{1ec}', 'O'),
+    ('\u{1ed}', 'o'),
+    ('\u{1f0}', 'j'),
+    ('\u{1f4}', 'G'),
+    ('\u{1f5}', 'g'),
+    ('\u{1f8}', 'N'),
+    ('\u{1f9}', 'n'),
+    ('\u{1fa}', 'A'),
+    ('\u{1fb}', 'a'),
+    ('\u{200}', 'A'),
+    ('\u{201}', 'a'),
+    ('\u{202}', 'A'),
+    ('\u{203}', 'a'),
+    ('\u{204}', 'E'),
+    ('\u{205}', 'e'),
+    ('\u{206}', 'E'),
+    ('\u{207}', 'e'),
+    ('\u{208}', 'I'),
+    ('\u{209}', 'i'),
+    ('\u{20a}', 'I'),
+    ('\u{20b}', 'i'),
+    ('\u{20c}', 'O'),
+    ('\u{20d}', 'o'),
+    ('\u{20e}', 'O'),
+    ('\u{20f}', 'o'),
+    ('\u{210}', 'R'),
+    ('\u{211}', 'r'),
+    ('\u{212}', 'R'),
+    ('\u{213}', 'r'),
+    ('\u{214}', 'U'),
+    ('\u{215}', 'u'),
+    ('\u{216}', 'U'),
+    ('\u{217}', 'u'),
+    ('\u{218}', 'S'),
+    ('\u{219}', 's'),
+    ('\u{21a}', 'T'),
+    ('\u{21b}', 't'),
+    ('\u{21e}', 'H'),
+    ('\u{21f}', 'h'),
+    ('\u{226}', 'A'),
+    ('\u{227}', 'a'),
+    ('\u{228}', 'E'),
+    ('\u{229}', 'e'),
+    ('\u{22a}', 'O'),
+    ('\u{22b}', 'o'),
+    ('\u{22c}', 'O'),
+    ('\u{22d}', 'o'),
+    ('\u{22e}', 'O'),
+    ('\u{22f}', 'o'),
+    ('\u{230}', 'O'),
+    ('\u{231}', 'o'),
+    ('\u{232}', 'Y'),
+    ('\u{233}', 'y'),
+    ('\u{2010}', '-'),
+    ('\u{2011}', '-'),
+    ('\u{2012}', '-'),
+    ('\u{2013}', '-'),
+    ('\u{2014}', '-'),
+    ('\u{2018}', '\''),
+    ('\u{2019}', '\''),
+    ('\u{201a}', ','),
+    ('\u{201b}', '\''),
+    ('\u{201c}', '"'),
+    ('\u{201d}', '"'),
+    ('\u{201e}', '"'),
+    ('\u{2022}', '\u{b7}'),
+    ('\u{2026}', '.'),
+    ('\u{2032}', '\''),
+    ('\u{2033}', '"'),
+    ('\u{2039}', '<'),
+    ('\u{203a}', '>'),
+    ('\u{2190}', ICON_ARROW_LEFT),
+    ('\u{2191}', ICON_ARROW_UP),
+    ('\u{2192}', ICON_ARROW_RIGHT),
+    ('\u{2193}', ICON_ARROW_DOWN),
+    ('\u{2212}', '-'),
+    ('\u{2600}', ICON_SUN),
+    ('\u{2605}', ICON_STAR),
+    ('\u{2606}', ICON_STAR),
+    ('\u{263a}', ICON_SMILEY),
+    ('\u{263b}', ICON_SMILEY),
+    ('\u{2665}', ICON_HEART),
+    ('\u{266a}', ICON_NOTE),
+    ('\u{266b}', ICON_NOTE),
+    ('\u{26a0}', ICON_WARNING),
+    ('\u{2713}', ICON_CHECK),
+    ('\u{2714}', ICON_CHECK),
+    ('\u{2715}', ICON_CROSS),
+    ('\u{2716}', ICON_CROSS),
+    ('\u{2717}', ICON_CROSS),
+    ('\u{2718}', ICON_CROSS),
+    ('\u{274c}', ICON_CROSS),
+    ('\u{2764}', ICON_HEART),
+    ('\u{27a1}', ICON_ARROW_RIGHT),
+    ('\u{2b05}', ICON_ARROW_LEFT),
+    ('\u{2b06}', ICON_ARROW_UP),
+    ('\u{2b07}', ICON_ARROW_DOWN),
+    ('\u{2b50}', ICON_STAR),
+    ('\u{1f31e}', ICON_SUN),
+    ('\u{1f3b5}', ICON_NOTE),
+    ('\u{1f3b6}', ICON_NOTE),
+    ('\u{1f496}', ICON_HEART),
+    ('\u{1f600}', ICON_SMILEY),
+    ('\u{1f60a}', ICON_SMILEY),
+    ('\u{1f642}', ICON_SMILEY),
+];
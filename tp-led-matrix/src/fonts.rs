@@ -0,0 +1,413 @@
+//! Proportional fonts derived from the public domain X11 misc-fixed fonts by
+//! removing the empty columns around every glyph. They cover printable ASCII,
+//! Latin-1, the other letters of French (`Œ`, `œ`, `Ÿ`) and `€`, and also
+//! draw the icons of `crate::font` and the replacement character.
+
+use crate::font::{Font, Glyphs};
+
+/// 3x5 capitals in 6 rows, descenders included
+pub const FONT_3X5: Font = Font {
+    height: 6,
+    baseline: 4,
+    spacing: 1,
+    tables: &[
+        // Printable ASCII
+        Glyphs {
+            first: ' ',
+            offsets: &[
+                0, 2, 3, 6, 10, 13, 16, 20, 21, 23, 25, 28, 31, 33, 36, 37, 40, 43, 46, 49, 52, 55,
+                58, 61, 64, 67, 70, 71, 73, 76, 79, 82, 85, 88, 91, 94, 97, 100, 103, 106, 109,
+                112, 115, 118, 121, 124, 127, 130, 133, 136, 139, 142, 145, 148, 151, 154, 157,
+                160, 163, 166, 168, 171, 173, 176, 179, 181, 184, 187, 190, 193, 196, 199, 202,
+                205, 208, 211, 214, 217, 220, 223, 226, 229, 232, 235, 238, 241, 244, 247, 250,
+                253, 256, 259, 262, 263, 266, 270,
+            ],
+            columns: &[
+                0x00, 0x00, 0x17, 0x03, 0x00, 0x03, 0x1f, 0x0a, 0x1f, 0x0a, 0x16, 0x37, 0x1a, 0x09,
+                0x04, 0x12, 0x0a, 0x15, 0x0a, 0x10, 0x03, 0x1e, 0x21, 0x21, 0x1e, 0x15, 0x0e, 0x15,
+                0x04, 0x1f, 0x04, 0x20, 0x10, 0x04, 0x04, 0x04, 0x10, 0x18, 0x04, 0x03, 0x0e, 0x15,
+                0x0e, 0x12, 0x1f, 0x10, 0x12, 0x19, 0x16, 0x11, 0x15, 0x0b, 0x07, 0x04, 0x1f, 0x17,
+                0x15, 0x09, 0x0e, 0x15, 0x09, 0x19, 0x05, 0x03, 0x1a, 0x15, 0x0b, 0x12, 0x15, 0x0e,
+                0x12, 0x20, 0x12, 0x04, 0x0a, 0x11, 0x0a, 0x0a, 0x0a, 0x11, 0x0a, 0x04, 0x01, 0x15,
+                0x02, 0x0e, 0x11, 0x17, 0x1e, 0x05, 0x1e, 0x1f, 0x15, 0x0a, 0x0e, 0x11, 0x0a, 0x1f,
+                0x11, 0x0e, 0x1f, 0x15, 0x11, 0x1f, 0x05, 0x01, 0x0e, 0x11, 0x1d, 0x1f, 0x04, 0x1f,
+                0x11, 0x1f, 0x11, 0x08, 0x10, 0x0f, 0x1f, 0x04, 0x1b, 0x1f, 0x10, 0x10, 0x1f, 0x06,
+                0x1f, 0x1f, 0x0e, 0x1f, 0x0e, 0x11, 0x0e, 0x1f, 0x05, 0x02, 0x0e, 0x11, 0x2e, 0x1f,
+                0x05, 0x1a, 0x12, 0x15, 0x09, 0x01, 0x1f, 0x01, 0x1f, 0x10, 0x1f, 0x0f, 0x18, 0x0f,
+                0x1f, 0x0c, 0x1f, 0x1b, 0x04, 0x1b, 0x03, 0x1c, 0x03, 0x19, 0x15, 0x13, 0x1f, 0x11,
+                0x03, 0x04, 0x18, 0x11, 0x1f, 0x02, 0x01, 0x02, 0x20, 0x20, 0x20, 0x01, 0x02, 0x0c,
+                0x12, 0x1e, 0x1f, 0x12, 0x0c, 0x0c, 0x12, 0x12, 0x0c, 0x12, 0x1f, 0x0c, 0x1a, 0x14,
+                0x04, 0x1e, 0x05, 0x24, 0x2a, 0x1e, 0x1f, 0x02, 0x1c, 0x14, 0x1d, 0x10, 0x20, 0x20,
+                0x1d, 0x1f, 0x04, 0x1a, 0x11, 0x1f, 0x10, 0x1e, 0x04, 0x1e, 0x1e, 0x02, 0x1c, 0x0c,
+                0x12, 0x0c, 0x3e, 0x0a, 0x04, 0x0c, 0x12, 0x3e, 0x1e, 0x04, 0x02, 0x14, 0x16, 0x0a,
+                0x02, 0x0f, 0x12, 0x0e, 0x10, 0x1e, 0x0e, 0x10, 0x0e, 0x1e, 0x08, 0x1e, 0x12, 0x0c,
+                0x12, 0x26, 0x28, 0x1e, 0x12, 0x1a, 0x16, 0x04, 0x1e, 0x21, 0x1f, 0x21, 0x1e, 0x04,
+                0x02, 0x01, 0x02, 0x01,
+            ],
+        },
+        // Latin-1 supplement
+        Glyphs {
+            first: '\u{a0}',
+            offsets: &[
+                0, 2, 3, 6, 9, 13, 16, 17, 20, 23, 27, 30, 34, 37, 40, 44, 47, 50, 53, 55, 57, 59,
+                62, 66, 67, 69, 71, 74, 78, 82, 86, 90, 93, 96, 99, 102, 105, 108, 111, 115, 118,
+                121, 124, 127, 130, 133, 136, 139, 142, 146, 150, 153, 156, 159, 163, 166, 169,
+                172, 175, 178, 181, 184, 187, 190, 193, 196, 199, 202, 206, 209, 212, 216, 219,
+                222, 225, 228, 231, 234, 237, 240, 243, 246, 250, 253, 256, 259, 262, 265, 268,
+                271, 274, 277, 280, 283, 286, 289, 292,
+            ],
+            columns: &[
+                0x00, 0x00, 0x1d, 0x0e, 0x1b, 0x0a, 0x10, 0x0e, 0x15, 0x12, 0x0c, 0x0c, 0x12, 0x05,
+                0x1e, 0x05, 0x1b, 0x26, 0x2b, 0x1d, 0x01, 0x00, 0x01, 0x1e, 0x2d, 0x21, 0x1e, 0x12,
+                0x15, 0x17, 0x04, 0x0a, 0x04, 0x0a, 0x04, 0x04, 0x0c, 0x04, 0x04, 0x04, 0x06, 0x0f,
+                0x0b, 0x06, 0x01, 0x01, 0x01, 0x02, 0x05, 0x02, 0x12, 0x17, 0x12, 0x0d, 0x0b, 0x15,
+                0x0b, 0x02, 0x01, 0x3e, 0x10, 0x0e, 0x06, 0x1f, 0x01, 0x1f, 0x04, 0x10, 0x08, 0x02,
+                0x0f, 0x12, 0x15, 0x12, 0x0a, 0x04, 0x0a, 0x04, 0x07, 0x18, 0x10, 0x38, 0x07, 0x00,
+                0x34, 0x2c, 0x15, 0x0b, 0x10, 0x38, 0x08, 0x15, 0x10, 0x1d, 0x0a, 0x1c, 0x1c, 0x0a,
+                0x1d, 0x1d, 0x0b, 0x1c, 0x1e, 0x0b, 0x1d, 0x1d, 0x0a, 0x1d, 0x1c, 0x0b, 0x1c, 0x1e,
+                0x05, 0x1f, 0x15, 0x2e, 0x11, 0x0a, 0x1f, 0x16, 0x12, 0x1e, 0x16, 0x13, 0x1e, 0x17,
+                0x13, 0x1f, 0x16, 0x13, 0x13, 0x1e, 0x12, 0x12, 0x1e, 0x13, 0x12, 0x1f, 0x12, 0x11,
+                0x1e, 0x11, 0x15, 0x1f, 0x11, 0x0e, 0x1e, 0x0d, 0x1e, 0x01, 0x0d, 0x12, 0x0c, 0x0c,
+                0x12, 0x0d, 0x0c, 0x13, 0x0c, 0x0e, 0x13, 0x0f, 0x01, 0x0d, 0x12, 0x0d, 0x0a, 0x04,
+                0x0a, 0x1e, 0x15, 0x0f, 0x1d, 0x12, 0x1c, 0x1c, 0x12, 0x1d, 0x1c, 0x11, 0x1c, 0x1d,
+                0x10, 0x1d, 0x04, 0x18, 0x05, 0x1f, 0x0a, 0x04, 0x3e, 0x15, 0x1a, 0x09, 0x16, 0x1c,
+                0x08, 0x16, 0x1d, 0x08, 0x15, 0x1d, 0x0a, 0x15, 0x1e, 0x01, 0x09, 0x14, 0x1d, 0x08,
+                0x15, 0x1c, 0x0c, 0x12, 0x1e, 0x16, 0x0c, 0x32, 0x14, 0x0d, 0x1a, 0x14, 0x0c, 0x1a,
+                0x15, 0x0d, 0x1b, 0x14, 0x0d, 0x1a, 0x15, 0x11, 0x1e, 0x10, 0x12, 0x1e, 0x11, 0x12,
+                0x1d, 0x12, 0x15, 0x1c, 0x11, 0x09, 0x16, 0x0d, 0x1e, 0x05, 0x1a, 0x01, 0x09, 0x16,
+                0x08, 0x08, 0x16, 0x09, 0x08, 0x15, 0x08, 0x09, 0x15, 0x09, 0x09, 0x14, 0x09, 0x04,
+                0x15, 0x04, 0x1c, 0x12, 0x0e, 0x0d, 0x12, 0x1c, 0x0c, 0x12, 0x1d, 0x0c, 0x11, 0x1c,
+                0x0d, 0x10, 0x1d, 0x2c, 0x2a, 0x1d, 0x3f, 0x14, 0x08, 0x2d, 0x28, 0x1d,
+            ],
+        },
+        Glyphs {
+            first: '\u{152}',
+            offsets: &[0, 4, 9],
+            columns: &[0x0e, 0x11, 0x1e, 0x15, 0x0c, 0x12, 0x0c, 0x1a, 0x14],
+        },
+        Glyphs {
+            first: '\u{178}',
+            offsets: &[0, 3],
+            columns: &[0x05, 0x18, 0x05],
+        },
+        Glyphs {
+            first: '\u{20ac}',
+            offsets: &[0, 3],
+            columns: &[0x04, 0x0e, 0x15],
+        },
+        // Icons
+        Glyphs {
+            first: '\u{e000}',
+            offsets: &[0, 5, 10, 15, 20, 25, 30, 35, 40, 45, 50, 55, 60],
+            columns: &[
+                0x07, 0x0f, 0x1e, 0x0f, 0x07, 0x12, 0x0e, 0x07, 0x0e, 0x12, 0x0e, 0x15, 0x17, 0x15,
+                0x0e, 0x18, 0x1f, 0x01, 0x19, 0x1f, 0x15, 0x0e, 0x1f, 0x0e, 0x15, 0x0c, 0x18, 0x0c,
+                0x06, 0x03, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x18, 0x1e, 0x09, 0x1e, 0x18, 0x04, 0x02,
+                0x1f, 0x02, 0x04, 0x04, 0x08, 0x1f, 0x08, 0x04, 0x04, 0x0e, 0x15, 0x04, 0x04, 0x04,
+                0x04, 0x15, 0x0e, 0x04,
+            ],
+        },
+        Glyphs {
+            first: '\u{fffd}',
+            offsets: &[0, 3],
+            columns: &[0x1f, 0x11, 0x1f],
+        },
+    ],
+    kerning: &[
+        ('F', ',', -1),
+        ('F', '.', -1),
+        ('L', '"', -1),
+        ('L', '\'', -1),
+        ('L', 'T', -1),
+        ('L', 'Y', -1),
+        ('P', ',', -1),
+        ('P', '.', -1),
+        ('T', ',', -1),
+        ('T', '.', -1),
+        ('V', ',', -1),
+        ('Y', ',', -1),
+        ('Y', '.', -1),
+        ('r', ',', -1),
+        ('r', '.', -1),
+    ],
+};
+
+/// 5x7 cells, descenders included. Accents over capitals are flattened to
+/// fit.
+pub const FONT_5X7: Font = Font {
+    height: 7,
+    baseline: 5,
+    spacing: 1,
+    tables: &[
+        // Printable ASCII
+        Glyphs {
+            first: ' ',
+            offsets: &[
+                0, 3, 4, 7, 12, 17, 21, 25, 26, 28, 30, 33, 38, 41, 45, 47, 51, 54, 57, 61, 65, 69,
+                73, 77, 81, 85, 89, 91, 94, 97, 101, 104, 107, 111, 115, 119, 123, 127, 131, 135,
+                139, 143, 146, 150, 154, 158, 162, 166, 170, 174, 178, 182, 186, 189, 193, 197,
+                201, 205, 208, 212, 215, 219, 222, 225, 229, 231, 235, 239, 242, 246, 250, 254,
+                258, 262, 265, 268, 272, 275, 279, 283, 287, 291, 295, 299, 303, 307, 311, 314,
+                318, 322, 326, 330, 333, 334, 337, 341,
+            ],
+            columns: &[
+                0x00, 0x00, 0x00, 0x2f, 0x07, 0x00, 0x07, 0x14, 0x3e, 0x14, 0x3e, 0x14, 0x04, 0x2a,
+                0x3e, 0x2a, 0x10, 0x13, 0x08, 0x04, 0x32, 0x14, 0x2a, 0x14, 0x20, 0x07, 0x1e, 0x21,
+                0x21, 0x1e, 0x2a, 0x1c, 0x2a, 0x08, 0x08, 0x3e, 0x08, 0x08, 0x40, 0x30, 0x10, 0x08,
+                0x08, 0x08, 0x08, 0x30, 0x30, 0x10, 0x08, 0x04, 0x02, 0x1e, 0x21, 0x1e, 0x22, 0x3f,
+                0x20, 0x22, 0x31, 0x29, 0x26, 0x11, 0x25, 0x25, 0x1b, 0x0c, 0x0a, 0x3f, 0x08, 0x17,
+                0x25, 0x25, 0x19, 0x1e, 0x25, 0x25, 0x18, 0x01, 0x31, 0x0d, 0x03, 0x1a, 0x25, 0x25,
+                0x1a, 0x06, 0x29, 0x29, 0x1e, 0x36, 0x36, 0x40, 0x36, 0x16, 0x08, 0x14, 0x22, 0x14,
+                0x14, 0x14, 0x14, 0x22, 0x14, 0x08, 0x02, 0x29, 0x06, 0x1e, 0x21, 0x2d, 0x0e, 0x3e,
+                0x09, 0x09, 0x3e, 0x3f, 0x25, 0x25, 0x1a, 0x1e, 0x21, 0x21, 0x12, 0x3f, 0x21, 0x21,
+                0x1e, 0x3f, 0x25, 0x25, 0x21, 0x3f, 0x05, 0x05, 0x01, 0x1e, 0x21, 0x29, 0x3a, 0x3f,
+                0x04, 0x04, 0x3f, 0x21, 0x3f, 0x21, 0x10, 0x20, 0x20, 0x1f, 0x3f, 0x0c, 0x12, 0x21,
+                0x3f, 0x20, 0x20, 0x20, 0x3f, 0x06, 0x06, 0x3f, 0x3f, 0x06, 0x18, 0x3f, 0x1e, 0x21,
+                0x21, 0x1e, 0x3f, 0x09, 0x09, 0x06, 0x1e, 0x31, 0x21, 0x5e, 0x3f, 0x09, 0x19, 0x26,
+                0x12, 0x25, 0x29, 0x12, 0x01, 0x3f, 0x01, 0x1f, 0x20, 0x20, 0x1f, 0x0f, 0x30, 0x30,
+                0x0f, 0x3f, 0x18, 0x18, 0x3f, 0x33, 0x0c, 0x0c, 0x33, 0x07, 0x38, 0x07, 0x31, 0x29,
+                0x25, 0x23, 0x3f, 0x21, 0x21, 0x02, 0x04, 0x08, 0x10, 0x21, 0x21, 0x3f, 0x02, 0x01,
+                0x02, 0x20, 0x20, 0x20, 0x20, 0x01, 0x02, 0x18, 0x24, 0x14, 0x3c, 0x3f, 0x24, 0x24,
+                0x18, 0x18, 0x24, 0x24, 0x18, 0x24, 0x24, 0x3f, 0x18, 0x34, 0x2c, 0x08, 0x08, 0x3e,
+                0x09, 0x02, 0x28, 0x54, 0x54, 0x4c, 0x3f, 0x04, 0x04, 0x38, 0x24, 0x3d, 0x20, 0x20,
+                0x40, 0x3d, 0x3f, 0x08, 0x14, 0x20, 0x21, 0x3f, 0x20, 0x3c, 0x08, 0x0c, 0x38, 0x3c,
+                0x04, 0x04, 0x38, 0x18, 0x24, 0x24, 0x18, 0x7c, 0x24, 0x24, 0x18, 0x18, 0x24, 0x24,
+                0x7c, 0x3c, 0x04, 0x04, 0x08, 0x28, 0x2c, 0x34, 0x14, 0x04, 0x1f, 0x24, 0x20, 0x1c,
+                0x20, 0x20, 0x3c, 0x1c, 0x20, 0x1c, 0x3c, 0x30, 0x30, 0x3c, 0x24, 0x18, 0x18, 0x24,
+                0x0c, 0x50, 0x20, 0x1c, 0x24, 0x34, 0x2c, 0x24, 0x04, 0x1e, 0x21, 0x3f, 0x21, 0x1e,
+                0x04, 0x02, 0x01, 0x02, 0x01,
+            ],
+        },
+        // Latin-1 supplement
+        Glyphs {
+            first: '\u{a0}',
+            offsets: &[
+                0, 3, 4, 8, 12, 17, 20, 21, 24, 27, 32, 35, 40, 44, 47, 52, 56, 59, 64, 66, 68, 70,
+                74, 78, 80, 82, 85, 88, 93, 97, 101, 105, 108, 112, 116, 120, 124, 128, 132, 136,
+                140, 144, 148, 152, 156, 159, 162, 165, 168, 172, 176, 180, 184, 188, 192, 196,
+                200, 204, 208, 212, 216, 220, 223, 227, 231, 235, 239, 243, 247, 251, 255, 259,
+                262, 266, 270, 274, 278, 281, 284, 287, 290, 294, 298, 302, 306, 310, 314, 318,
+                322, 326, 330, 334, 338, 342, 346, 350, 354,
+            ],
+            columns: &[
+                0x00, 0x00, 0x00, 0x3d, 0x18, 0x24, 0x7e, 0x24, 0x28, 0x1c, 0x2a, 0x22, 0x22, 0x1c,
+                0x14, 0x1c, 0x22, 0x0b, 0x3c, 0x0b, 0x36, 0x4e, 0x55, 0x39, 0x01, 0x00, 0x01, 0x3e,
+                0x49, 0x55, 0x41, 0x3e, 0x02, 0x05, 0x07, 0x08, 0x14, 0x00, 0x08, 0x14, 0x08, 0x08,
+                0x08, 0x18, 0x08, 0x08, 0x08, 0x3e, 0x5d, 0x45, 0x41, 0x3e, 0x01, 0x01, 0x01, 0x01,
+                0x02, 0x05, 0x02, 0x24, 0x24, 0x3f, 0x24, 0x24, 0x0d, 0x0b, 0x0b, 0x0f, 0x02, 0x01,
+                0x7c, 0x20, 0x20, 0x1c, 0x06, 0x3f, 0x01, 0x3f, 0x0c, 0x0c, 0x40, 0x20, 0x0a, 0x0f,
+                0x08, 0x02, 0x05, 0x02, 0x14, 0x08, 0x00, 0x14, 0x08, 0x0f, 0x20, 0x30, 0x78, 0x0f,
+                0x00, 0x68, 0x58, 0x0b, 0x2f, 0x30, 0x78, 0x18, 0x25, 0x10, 0x3d, 0x0b, 0x0a, 0x3c,
+                0x3c, 0x0a, 0x0b, 0x3d, 0x3c, 0x0b, 0x0b, 0x3d, 0x3d, 0x0a, 0x0b, 0x3d, 0x3d, 0x0a,
+                0x0a, 0x3d, 0x3c, 0x0b, 0x0b, 0x3c, 0x3e, 0x09, 0x3f, 0x25, 0x1e, 0x61, 0x21, 0x12,
+                0x3f, 0x2b, 0x2a, 0x22, 0x3e, 0x2a, 0x2b, 0x23, 0x3e, 0x2b, 0x2b, 0x23, 0x3f, 0x2a,
+                0x2a, 0x23, 0x23, 0x3f, 0x22, 0x22, 0x3f, 0x23, 0x23, 0x3f, 0x23, 0x23, 0x3e, 0x23,
+                0x25, 0x3f, 0x21, 0x1e, 0x3f, 0x04, 0x19, 0x3f, 0x1d, 0x23, 0x22, 0x1c, 0x1c, 0x22,
+                0x23, 0x1d, 0x1c, 0x23, 0x23, 0x1d, 0x1d, 0x22, 0x23, 0x1d, 0x1d, 0x22, 0x22, 0x1d,
+                0x24, 0x18, 0x18, 0x24, 0x3e, 0x39, 0x27, 0x1f, 0x1f, 0x21, 0x20, 0x1e, 0x1e, 0x20,
+                0x21, 0x1f, 0x1e, 0x21, 0x21, 0x1f, 0x1d, 0x20, 0x20, 0x1d, 0x0e, 0x31, 0x0f, 0x3f,
+                0x0a, 0x0a, 0x04, 0x3e, 0x01, 0x25, 0x1a, 0x18, 0x25, 0x16, 0x3c, 0x18, 0x26, 0x15,
+                0x3c, 0x18, 0x26, 0x15, 0x3e, 0x1a, 0x25, 0x16, 0x3d, 0x18, 0x25, 0x14, 0x3d, 0x18,
+                0x27, 0x17, 0x3c, 0x18, 0x24, 0x3c, 0x2c, 0x18, 0x64, 0x24, 0x18, 0x35, 0x2e, 0x08,
+                0x18, 0x36, 0x2d, 0x08, 0x1a, 0x35, 0x2e, 0x08, 0x19, 0x34, 0x2d, 0x08, 0x25, 0x3e,
+                0x20, 0x26, 0x3d, 0x20, 0x26, 0x3d, 0x22, 0x25, 0x3c, 0x21, 0x18, 0x25, 0x26, 0x1a,
+                0x3e, 0x05, 0x06, 0x39, 0x18, 0x25, 0x26, 0x18, 0x18, 0x26, 0x25, 0x18, 0x18, 0x25,
+                0x25, 0x18, 0x1a, 0x25, 0x26, 0x19, 0x18, 0x25, 0x24, 0x19, 0x08, 0x2a, 0x2a, 0x08,
+                0x38, 0x34, 0x2c, 0x1c, 0x1c, 0x21, 0x22, 0x3c, 0x1c, 0x22, 0x21, 0x3c, 0x1c, 0x21,
+                0x21, 0x3c, 0x1c, 0x21, 0x20, 0x3d, 0x0c, 0x52, 0x21, 0x1c, 0x7e, 0x24, 0x24, 0x18,
+                0x0c, 0x51, 0x20, 0x1d,
+            ],
+        },
+        Glyphs {
+            first: '\u{152}',
+            offsets: &[0, 4, 9],
+            columns: &[0x1e, 0x21, 0x3f, 0x25, 0x18, 0x24, 0x18, 0x34, 0x28],
+        },
+        Glyphs {
+            first: '\u{178}',
+            offsets: &[0, 3],
+            columns: &[0x0f, 0x30, 0x0f],
+        },
+        Glyphs {
+            first: '\u{20ac}',
+            offsets: &[0, 4],
+            columns: &[0x0c, 0x1e, 0x2d, 0x21],
+        },
+        // Icons
+        Glyphs {
+            first: '\u{e000}',
+            offsets: &[0, 7, 14, 21, 27, 34, 41, 48, 55, 60, 65, 71, 77],
+            columns: &[
+                0x0e, 0x1f, 0x3f, 0x7e, 0x3f, 0x1f, 0x0e, 0x04, 0x6c, 0x3c, 0x1f, 0x3c, 0x6c, 0x04,
+                0x3e, 0x6f, 0x5b, 0x5f, 0x5b, 0x6f, 0x3e, 0x60, 0x7f, 0x03, 0x03, 0x63, 0x7f, 0x08,
+                0x22, 0x1c, 0x5d, 0x1c, 0x22, 0x08, 0x08, 0x18, 0x30, 0x18, 0x0c, 0x06, 0x02, 0x22,
+                0x36, 0x1c, 0x08, 0x1c, 0x36, 0x22, 0x60, 0x78, 0x7e, 0x51, 0x7e, 0x78, 0x60, 0x04,
+                0x06, 0x3f, 0x06, 0x04, 0x08, 0x18, 0x3f, 0x18, 0x08, 0x08, 0x1c, 0x3e, 0x08, 0x08,
+                0x08, 0x08, 0x08, 0x08, 0x3e, 0x1c, 0x08,
+            ],
+        },
+        Glyphs {
+            first: '\u{fffd}',
+            offsets: &[0, 5],
+            columns: &[0x3f, 0x21, 0x21, 0x21, 0x3f],
+        },
+    ],
+    kerning: &[
+        ('F', ',', -1),
+        ('F', '.', -1),
+        ('L', '"', -1),
+        ('L', '\'', -1),
+        ('L', 'T', -1),
+        ('L', 'V', -1),
+        ('L', 'Y', -1),
+        ('P', ',', -1),
+        ('P', '.', -1),
+        ('T', ',', -1),
+        ('T', '.', -1),
+        ('V', ',', -1),
+        ('Y', ',', -1),
+        ('Y', '.', -1),
+        ('r', ',', -1),
+    ],
+};
+
+/// 6x8 cells, descenders included, filling the height of the panel. Accents
+/// over capitals are flattened to fit.
+pub const FONT_6X8: Font = Font {
+    height: 8,
+    baseline: 5,
+    spacing: 1,
+    tables: &[
+        // Printable ASCII
+        Glyphs {
+            first: ' ',
+            offsets: &[
+                0, 3, 4, 7, 12, 17, 23, 28, 29, 31, 33, 38, 43, 45, 50, 52, 56, 60, 63, 67, 71, 76,
+                80, 84, 88, 92, 96, 98, 100, 105, 110, 115, 119, 124, 129, 134, 138, 142, 146, 150,
+                154, 158, 161, 166, 170, 174, 179, 183, 188, 192, 196, 200, 204, 209, 213, 217,
+                222, 227, 232, 236, 239, 243, 246, 251, 256, 258, 262, 266, 270, 274, 278, 282,
+                286, 290, 293, 296, 300, 303, 308, 312, 316, 320, 324, 328, 332, 336, 340, 344,
+                349, 353, 357, 361, 364, 365, 368, 372,
+            ],
+            columns: &[
+                0x00, 0x00, 0x00, 0x2f, 0x07, 0x00, 0x07, 0x14, 0x7f, 0x14, 0x7f, 0x14, 0x26, 0x49,
+                0xff, 0x49, 0x32, 0x01, 0x33, 0x09, 0x24, 0x53, 0x20, 0x36, 0x49, 0x49, 0x36, 0x50,
+                0x07, 0x3e, 0x41, 0x41, 0x3e, 0x2a, 0x1c, 0x08, 0x1c, 0x2a, 0x08, 0x08, 0x3e, 0x08,
+                0x08, 0x90, 0x70, 0x08, 0x08, 0x08, 0x08, 0x08, 0x30, 0x30, 0x30, 0x08, 0x04, 0x03,
+                0x1e, 0x21, 0x21, 0x1e, 0x22, 0x3f, 0x20, 0x22, 0x31, 0x29, 0x26, 0x21, 0x25, 0x27,
+                0x19, 0x18, 0x14, 0x12, 0x3f, 0x10, 0x27, 0x25, 0x25, 0x19, 0x1e, 0x25, 0x25, 0x18,
+                0x01, 0x31, 0x09, 0x07, 0x1a, 0x25, 0x25, 0x1a, 0x06, 0x29, 0x29, 0x1e, 0x36, 0x36,
+                0x96, 0x76, 0x08, 0x14, 0x14, 0x22, 0x22, 0x14, 0x14, 0x14, 0x14, 0x14, 0x22, 0x22,
+                0x14, 0x14, 0x08, 0x01, 0x2d, 0x05, 0x03, 0x1e, 0x21, 0x2d, 0x2b, 0x04, 0x3c, 0x0a,
+                0x09, 0x0a, 0x3c, 0x3f, 0x25, 0x25, 0x25, 0x1a, 0x1e, 0x21, 0x21, 0x12, 0x3f, 0x21,
+                0x21, 0x1e, 0x3f, 0x25, 0x25, 0x21, 0x3f, 0x05, 0x05, 0x01, 0x1e, 0x21, 0x29, 0x1a,
+                0x3f, 0x04, 0x04, 0x3f, 0x21, 0x3f, 0x21, 0x10, 0x20, 0x21, 0x1f, 0x01, 0x3f, 0x04,
+                0x0a, 0x31, 0x3f, 0x20, 0x20, 0x20, 0x3f, 0x02, 0x0c, 0x02, 0x3f, 0x3f, 0x02, 0x04,
+                0x3f, 0x1e, 0x21, 0x21, 0x21, 0x1e, 0x3f, 0x09, 0x09, 0x06, 0x1e, 0x29, 0x31, 0x5e,
+                0x3f, 0x09, 0x09, 0x36, 0x12, 0x25, 0x29, 0x12, 0x01, 0x01, 0x3f, 0x01, 0x01, 0x1f,
+                0x20, 0x20, 0x1f, 0x0f, 0x38, 0x38, 0x0f, 0x3f, 0x10, 0x0c, 0x10, 0x3f, 0x21, 0x12,
+                0x0c, 0x12, 0x21, 0x03, 0x04, 0x38, 0x04, 0x03, 0x31, 0x29, 0x25, 0x23, 0x3f, 0x21,
+                0x21, 0x03, 0x04, 0x08, 0x30, 0x21, 0x21, 0x3f, 0x04, 0x02, 0x01, 0x02, 0x04, 0x80,
+                0x80, 0x80, 0x80, 0x80, 0x01, 0x02, 0x18, 0x24, 0x24, 0x3c, 0x3f, 0x24, 0x24, 0x18,
+                0x18, 0x24, 0x24, 0x24, 0x18, 0x24, 0x24, 0x3f, 0x18, 0x34, 0x2c, 0x28, 0x08, 0x3e,
+                0x09, 0x02, 0x18, 0xa4, 0xa4, 0x78, 0x3f, 0x04, 0x04, 0x38, 0x24, 0x3d, 0x20, 0x40,
+                0x84, 0x7d, 0x3f, 0x08, 0x14, 0x20, 0x21, 0x3f, 0x20, 0x3c, 0x04, 0x18, 0x04, 0x38,
+                0x3c, 0x04, 0x04, 0x38, 0x18, 0x24, 0x24, 0x18, 0xfc, 0x24, 0x24, 0x18, 0x18, 0x24,
+                0x24, 0xfc, 0x3c, 0x08, 0x04, 0x08, 0x28, 0x2c, 0x34, 0x14, 0x04, 0x1f, 0x24, 0x10,
+                0x1c, 0x20, 0x20, 0x3c, 0x0c, 0x30, 0x30, 0x0c, 0x1c, 0x20, 0x18, 0x20, 0x1c, 0x24,
+                0x18, 0x18, 0x24, 0x5c, 0xa0, 0xa0, 0x7c, 0x24, 0x34, 0x2c, 0x24, 0x04, 0x1b, 0x21,
+                0x7f, 0x21, 0x1b, 0x04, 0x04, 0x02, 0x04, 0x02,
+            ],
+        },
+        // Latin-1 supplement
+        Glyphs {
+            first: '\u{a0}',
+            offsets: &[
+                0, 3, 4, 8, 13, 18, 23, 24, 28, 31, 37, 40, 45, 49, 53, 59, 63, 67, 72, 75, 78, 80,
+                84, 89, 90, 92, 95, 98, 103, 108, 113, 118, 122, 127, 132, 137, 142, 147, 152, 157,
+                161, 165, 169, 173, 177, 180, 183, 186, 189, 194, 198, 202, 206, 210, 214, 218,
+                223, 227, 231, 235, 239, 243, 248, 252, 256, 260, 264, 268, 272, 276, 280, 285,
+                289, 293, 297, 301, 305, 308, 311, 314, 317, 321, 325, 329, 333, 337, 341, 345,
+                350, 354, 358, 362, 366, 370, 374, 378, 382,
+            ],
+            columns: &[
+                0x00, 0x00, 0x00, 0x3d, 0x18, 0x24, 0x7e, 0x24, 0x48, 0x7e, 0x49, 0x49, 0x42, 0x2a,
+                0x14, 0x22, 0x14, 0x2a, 0x15, 0x16, 0x3c, 0x16, 0x15, 0x77, 0x4a, 0x55, 0x55, 0x29,
+                0x01, 0x00, 0x01, 0x1f, 0x21, 0x25, 0x2b, 0x21, 0x1f, 0x12, 0x15, 0x17, 0x08, 0x14,
+                0x2a, 0x14, 0x22, 0x08, 0x08, 0x08, 0x38, 0x08, 0x08, 0x08, 0x08, 0x1f, 0x21, 0x2f,
+                0x23, 0x21, 0x1f, 0x01, 0x01, 0x01, 0x01, 0x04, 0x0a, 0x0a, 0x04, 0x44, 0x44, 0x5f,
+                0x44, 0x44, 0x12, 0x19, 0x16, 0x11, 0x15, 0x0a, 0x02, 0x01, 0x7c, 0x20, 0x10, 0x3c,
+                0x06, 0x0f, 0x3f, 0x01, 0x3f, 0x08, 0x80, 0x40, 0x12, 0x1f, 0x10, 0x12, 0x15, 0x12,
+                0x22, 0x14, 0x2a, 0x14, 0x08, 0x01, 0x2f, 0x38, 0x7c, 0x20, 0x01, 0x0f, 0x48, 0x64,
+                0x58, 0x09, 0x2b, 0x3d, 0x7c, 0x20, 0x18, 0x24, 0x27, 0x10, 0x30, 0x0d, 0x0b, 0x0c,
+                0x30, 0x30, 0x0c, 0x0b, 0x0d, 0x30, 0x30, 0x0d, 0x0b, 0x0d, 0x30, 0x30, 0x0d, 0x0b,
+                0x0d, 0x31, 0x30, 0x0d, 0x0a, 0x0d, 0x30, 0x30, 0x0d, 0x0b, 0x0d, 0x30, 0x3e, 0x05,
+                0x3f, 0x25, 0x21, 0x1e, 0xa1, 0x61, 0x12, 0x3f, 0x2b, 0x2a, 0x22, 0x3e, 0x2a, 0x2b,
+                0x23, 0x3e, 0x2b, 0x2b, 0x23, 0x3e, 0x2b, 0x2a, 0x23, 0x23, 0x3f, 0x22, 0x22, 0x3f,
+                0x23, 0x23, 0x3f, 0x23, 0x23, 0x3e, 0x23, 0x04, 0x3f, 0x25, 0x21, 0x1e, 0x3f, 0x05,
+                0x09, 0x3f, 0x1d, 0x23, 0x22, 0x1c, 0x1c, 0x22, 0x23, 0x1d, 0x1c, 0x23, 0x23, 0x1d,
+                0x1d, 0x23, 0x23, 0x1d, 0x1c, 0x23, 0x22, 0x1d, 0x22, 0x14, 0x08, 0x14, 0x22, 0x7e,
+                0x39, 0x27, 0x1f, 0x1f, 0x21, 0x20, 0x1e, 0x1e, 0x20, 0x21, 0x1f, 0x1e, 0x21, 0x21,
+                0x1f, 0x1e, 0x21, 0x20, 0x1f, 0x02, 0x04, 0x39, 0x05, 0x02, 0x3f, 0x12, 0x12, 0x0c,
+                0x3e, 0x01, 0x2d, 0x12, 0x19, 0x25, 0x24, 0x3c, 0x18, 0x24, 0x25, 0x3d, 0x18, 0x25,
+                0x25, 0x3d, 0x19, 0x25, 0x25, 0x3d, 0x18, 0x25, 0x24, 0x3d, 0x18, 0x25, 0x27, 0x3d,
+                0x18, 0x24, 0x3c, 0x34, 0x28, 0x18, 0xa4, 0x64, 0x24, 0x19, 0x35, 0x2c, 0x28, 0x18,
+                0x34, 0x2d, 0x29, 0x18, 0x35, 0x2d, 0x29, 0x18, 0x35, 0x2c, 0x29, 0x25, 0x3d, 0x20,
+                0x24, 0x3d, 0x21, 0x25, 0x3d, 0x21, 0x25, 0x3c, 0x21, 0x18, 0x25, 0x25, 0x1f, 0x3d,
+                0x05, 0x05, 0x39, 0x19, 0x25, 0x24, 0x18, 0x18, 0x24, 0x25, 0x19, 0x18, 0x25, 0x25,
+                0x19, 0x19, 0x25, 0x25, 0x19, 0x18, 0x25, 0x24, 0x19, 0x08, 0x08, 0x2a, 0x08, 0x08,
+                0x38, 0x34, 0x2c, 0x1c, 0x1d, 0x21, 0x20, 0x3c, 0x1c, 0x20, 0x21, 0x3d, 0x1c, 0x21,
+                0x21, 0x3d, 0x1c, 0x21, 0x20, 0x3d, 0x5c, 0xa1, 0xa1, 0x7c, 0xff, 0x24, 0x24, 0x18,
+                0x5c, 0xa1, 0xa0, 0x7d,
+            ],
+        },
+        Glyphs {
+            first: '\u{152}',
+            offsets: &[0, 5, 10],
+            columns: &[0x1e, 0x21, 0x3f, 0x25, 0x21, 0x18, 0x24, 0x18, 0x34, 0x28],
+        },
+        Glyphs {
+            first: '\u{178}',
+            offsets: &[0, 5],
+            columns: &[0x02, 0x05, 0x38, 0x05, 0x02],
+        },
+        Glyphs {
+            first: '\u{20ac}',
+            offsets: &[0, 5],
+            columns: &[0x0c, 0x1e, 0x2d, 0x2d, 0x21],
+        },
+        // Icons
+        Glyphs {
+            first: '\u{e000}',
+            offsets: &[0, 7, 14, 21, 27, 34, 41, 48, 55, 60, 65, 71, 77],
+            columns: &[
+                0x0e, 0x1f, 0x3f, 0x7e, 0x3f, 0x1f, 0x0e, 0x04, 0x6c, 0x3c, 0x1f, 0x3c, 0x6c, 0x04,
+                0x3e, 0x6f, 0x5b, 0x5f, 0x5b, 0x6f, 0x3e, 0x60, 0x7f, 0x03, 0x03, 0x63, 0x7f, 0x08,
+                0x22, 0x1c, 0x5d, 0x1c, 0x22, 0x08, 0x08, 0x18, 0x30, 0x18, 0x0c, 0x06, 0x02, 0x22,
+                0x36, 0x1c, 0x08, 0x1c, 0x36, 0x22, 0x60, 0x78, 0x7e, 0x51, 0x7e, 0x78, 0x60, 0x04,
+                0x06, 0x3f, 0x06, 0x04, 0x08, 0x18, 0x3f, 0x18, 0x08, 0x08, 0x1c, 0x3e, 0x08, 0x08,
+                0x08, 0x08, 0x08, 0x08, 0x3e, 0x1c, 0x08,
+            ],
+        },
+        Glyphs {
+            first: '\u{fffd}',
+            offsets: &[0, 5],
+            columns: &[0x3f, 0x21, 0x21, 0x21, 0x3f],
+        },
+    ],
+    kerning: &[
+        ('F', ',', -1),
+        ('F', '.', -1),
+        ('L', '"', -1),
+        ('L', '\'', -1),
+        ('L', 'T', -1),
+        ('L', 'V', -1),
+        ('L', 'Y', -1),
+        ('P', ',', -1),
+        ('P', '.', -1),
+        ('T', ',', -1),
+        ('T', '.', -1),
+        ('Y', ',', -1),
+        ('Y', '.', -1),
+    ],
+};
@@ -0,0 +1,421 @@
+use crate::protocol::Frame;
+use crate::scale::{self, RgbImage};
+use crate::Color;
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The data does not start with `GIF87a` or `GIF89a`
+    NotGif,
+    /// The data stops in the middle of a block
+    UnexpectedEnd,
+    /// An image has no local colour table and the file has no global one
+    NoColorTable,
+    /// An image descriptor places the image outside of the logical screen
+    BadFrame,
+    /// The LZW compressed image data is corrupted
+    BadLzw,
+    /// A block starts with an unknown introducer byte
+    BadBlock(u8),
+    /// The logical screen has more than `MAX_PIXELS` pixels
+    TooLarge,
+}
+
+/// Largest logical screen decoded, whose canvas takes 3 MB
+pub const MAX_PIXELS: usize = 1 << 20;
+
+/// How the area of a frame is treated before the next frame is drawn
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Disposal {
+    /// Leave the frame in place
+    Keep,
+    /// Clear the area of the frame to the background colour
+    Background,
+    /// Restore the area of the frame to what it was before the frame
+    Previous,
+}
+
+/// A frame composited on the logical screen, at the GIF size
+pub struct GifFrame {
+    pub canvas: RgbImage,
+    pub delay_ms: u32,
+    pub disposal: Disposal,
+}
+
+/// Frame by frame GIF decoder. Every item is the full logical screen as it
+/// should be shown, after disposal of the previous frame and transparency
+/// have been handled.
+pub struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+    global_palette: Option<&'a [u8]>,
+    background: Color,
+    canvas: RgbImage,
+    /// Disposal of the last frame and the area it covered
+    pending: Option<(Disposal, usize, usize, usize, usize)>,
+    /// Canvas to go back to with `Disposal::Previous`
+    saved: Option<RgbImage>,
+    /// Number of times the animation should be played, 0 meaning forever
+    pub loop_count: Option<u16>,
+    done: bool,
+}
+
+/// Graphic control extension values applying to the next image
+#[derive(Copy, Clone)]
+struct Control {
+    delay_ms: u32,
+    disposal: Disposal,
+    transparent: Option<u8>,
+}
+
+impl Default for Control {
+    fn default() -> Self {
+        return Control {
+            delay_ms: 100,
+            disposal: Disposal::Keep,
+            transparent: None,
+        };
+    }
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        if data.len() < 13 {
+            return Err(Error::UnexpectedEnd);
+        }
+        if &data[..6] != b"GIF87a" && &data[..6] != b"GIF89a" {
+            return Err(Error::NotGif);
+        }
+        let width = u16::from_le_bytes([data[6], data[7]]) as usize;
+        let height = u16::from_le_bytes([data[8], data[9]]) as usize;
+        if width * height > MAX_PIXELS {
+            return Err(Error::TooLarge);
+        }
+        let packed = data[10];
+        let mut decoder = Decoder {
+            data,
+            pos: 13,
+            global_palette: None,
+            background: Color::default(),
+            canvas: RgbImage::new(width.max(1), height.max(1), Color::default()),
+            pending: None,
+            saved: None,
+            loop_count: None,
+            done: false,
+        };
+        if packed & 0x80 != 0 {
+            let palette = decoder.read(3 << ((packed & 0x07) + 1))?;
+            decoder.background = palette_color(palette, data[11]);
+            decoder.global_palette = Some(palette);
+        }
+        return Ok(decoder);
+    }
+
+    pub fn width(&self) -> usize {
+        return self.canvas.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.canvas.height;
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() - self.pos < len {
+            return Err(Error::UnexpectedEnd);
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        return Ok(bytes);
+    }
+
+    fn byte(&mut self) -> Result<u8, Error> {
+        return Ok(self.read(1)?[0]);
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        let bytes = self.read(2)?;
+        return Ok(u16::from_le_bytes([bytes[0], bytes[1]]));
+    }
+
+    /// Read a chain of data sub-blocks, appending their content to `out` if given
+    fn sub_blocks(&mut self, mut out: Option<&mut Vec<u8>>) -> Result<(), Error> {
+        loop {
+            let len = self.byte()? as usize;
+            if len == 0 {
+                return Ok(());
+            }
+            let block = self.read(len)?;
+            if let Some(out) = out.as_mut() {
+                out.extend_from_slice(block);
+            }
+        }
+    }
+
+    fn extension(&mut self, control: &mut Control) -> Result<(), Error> {
+        match self.byte()? {
+            0xf9 => {
+                let size = self.byte()? as usize;
+                let block = self.read(size)?;
+                if size < 4 {
+                    return Err(Error::UnexpectedEnd);
+                }
+                let packed = block[0];
+                control.disposal = match (packed >> 2) & 0x07 {
+                    2 => Disposal::Background,
+                    3 => Disposal::Previous,
+                    _ => Disposal::Keep,
+                };
+                // Like browsers do, very short delays are considered as unset
+                let delay = u16::from_le_bytes([block[1], block[2]]) as u32 * 10;
+                control.delay_ms = if delay < 20 { 100 } else { delay };
+                control.transparent = if packed & 0x01 != 0 {
+                    Some(block[3])
+                } else {
+                    None
+                };
+                return self.sub_blocks(None);
+            }
+            0xff => {
+                let mut content = Vec::new();
+                self.sub_blocks(Some(&mut content))?;
+                // NETSCAPE2.0 application block: identifier, then sub-block 1 with the loop count
+                if content.len() >= 14 && &content[..11] == b"NETSCAPE2.0" && content[11] == 1 {
+                    self.loop_count = Some(u16::from_le_bytes([content[12], content[13]]));
+                }
+                return Ok(());
+            }
+            _ => return self.sub_blocks(None),
+        }
+    }
+
+    fn dispose(&mut self) {
+        if let Some((disposal, left, top, width, height)) = self.pending.take() {
+            match disposal {
+                Disposal::Keep => {}
+                Disposal::Background => {
+                    for y in top..top + height {
+                        for x in left..left + width {
+                            self.canvas.set_pixel(x, y, self.background);
+                        }
+                    }
+                }
+                Disposal::Previous => {
+                    if let Some(saved) = self.saved.take() {
+                        self.canvas = saved;
+                    }
+                }
+            }
+        }
+    }
+
+    fn image(&mut self, control: Control) -> Result<GifFrame, Error> {
+        let left = self.u16()? as usize;
+        let top = self.u16()? as usize;
+        let width = self.u16()? as usize;
+        let height = self.u16()? as usize;
+        let packed = self.byte()?;
+        if left + width > self.canvas.width || top + height > self.canvas.height {
+            return Err(Error::BadFrame);
+        }
+        let palette = if packed & 0x80 != 0 {
+            self.read(3 << ((packed & 0x07) + 1))?
+        } else {
+            self.global_palette.ok_or(Error::NoColorTable)?
+        };
+        let interlaced = packed & 0x40 != 0;
+
+        let min_code_size = self.byte()?;
+        let mut compressed = Vec::new();
+        self.sub_blocks(Some(&mut compressed))?;
+        let indices = lzw_decode(min_code_size, &compressed, width * height)?;
+
+        self.dispose();
+        if control.disposal == Disposal::Previous {
+            self.saved = Some(self.canvas.clone());
+        }
+        for (i, &index) in indices.iter().enumerate() {
+            if Some(index) == control.transparent {
+                continue;
+            }
+            let y = if interlaced {
+                interlaced_row(i / width, height)
+            } else {
+                i / width
+            };
+            self.canvas
+                .set_pixel(left + i % width, top + y, palette_color(palette, index));
+        }
+        self.pending = Some((control.disposal, left, top, width, height));
+
+        return Ok(GifFrame {
+            canvas: self.canvas.clone(),
+            delay_ms: control.delay_ms,
+            disposal: control.disposal,
+        });
+    }
+}
+
+impl Iterator for Decoder<'_> {
+    type Item = Result<GifFrame, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut control = Control::default();
+        loop {
+            let result = match self.byte() {
+                Ok(0x21) => self.extension(&mut control).map(|_| None),
+                Ok(0x2c) => self.image(control).map(Some),
+                // Trailer
+                Ok(0x3b) => {
+                    self.done = true;
+                    return None;
+                }
+                Ok(other) => Err(Error::BadBlock(other)),
+                Err(error) => Err(error),
+            };
+            match result {
+                Ok(None) => continue,
+                Ok(Some(frame)) => return Some(Ok(frame)),
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
+
+/// Decode every frame of a GIF and scale it to the matrix with `options`
+pub fn to_frames(data: &[u8], options: &scale::Options) -> Result<Vec<Frame>, Error> {
+    let mut frames = Vec::new();
+    for frame in Decoder::new(data)? {
+        let frame = frame?;
+        frames.push(Frame {
+            image: scale::to_image(&frame.canvas, options),
+            delay_ms: frame.delay_ms,
+        });
+    }
+    return Ok(frames);
+}
+
+fn palette_color(palette: &[u8], index: u8) -> Color {
+    let i = 3 * index as usize;
+    if i + 2 >= palette.len() {
+        return Color::default();
+    }
+    return Color {
+        r: palette[i],
+        g: palette[i + 1],
+        b: palette[i + 2],
+    };
+}
+
+/// Row of the image stored at position `pass_row` in an interlaced image.
+/// Rows are stored every 8 rows from 0, every 8 rows from 4, every 4 rows
+/// from 2 and finally every 2 rows from 1.
+fn interlaced_row(mut pass_row: usize, height: usize) -> usize {
+    for (start, step) in [(0, 8), (4, 8), (2, 4), (1, 2)] {
+        let rows = (height + step - 1 - start) / step;
+        if pass_row < rows {
+            return start + pass_row * step;
+        }
+        pass_row -= rows;
+    }
+    return height - 1;
+}
+
+/// Decompress GIF LZW data into `len` colour indices
+fn lzw_decode(min_code_size: u8, data: &[u8], len: usize) -> Result<Vec<u8>, Error> {
+    if !(1..=11).contains(&min_code_size) {
+        return Err(Error::BadLzw);
+    }
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    // Each code is a previous code (prefix) followed by one index (suffix)
+    let mut prefix = [0u16; 4096];
+    let mut suffix = [0u8; 4096];
+    let mut first = [0u8; 4096];
+    let mut length = [0u16; 4096];
+    for code in 0..clear {
+        suffix[code as usize] = code as u8;
+        first[code as usize] = code as u8;
+        length[code as usize] = 1;
+    }
+
+    let mut out = Vec::with_capacity(len);
+    let mut code_size = min_code_size as u32 + 1;
+    let mut next = end + 1;
+    let mut previous: Option<u16> = None;
+    let (mut bits, mut bit_count) = (0u32, 0u32);
+    let mut bytes = data.iter();
+
+    while out.len() < len {
+        while bit_count < code_size {
+            match bytes.next() {
+                Some(&byte) => bits |= (byte as u32) << bit_count,
+                // Some encoders omit the end code
+                None => return Ok(finish(out, len)),
+            }
+            bit_count += 8;
+        }
+        let code = (bits & ((1 << code_size) - 1)) as u16;
+        bits >>= code_size;
+        bit_count -= code_size;
+
+        if code == clear {
+            code_size = min_code_size as u32 + 1;
+            next = end + 1;
+            previous = None;
+            continue;
+        }
+        if code == end {
+            break;
+        }
+        let Some(prev) = previous else {
+            if code >= clear {
+                return Err(Error::BadLzw);
+            }
+            out.push(code as u8);
+            previous = Some(code);
+            continue;
+        };
+
+        let start_char = if code < next {
+            first[code as usize]
+        } else if code == next {
+            first[prev as usize]
+        } else {
+            return Err(Error::BadLzw);
+        };
+        if next < 4096 {
+            prefix[next as usize] = prev;
+            suffix[next as usize] = start_char;
+            first[next as usize] = first[prev as usize];
+            length[next as usize] = length[prev as usize] + 1;
+            next += 1;
+            if next == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        }
+
+        // Write the string of `code` backwards from its last index
+        let start = out.len();
+        out.resize(start + length[code as usize] as usize, 0);
+        let mut c = code;
+        for slot in out[start..].iter_mut().rev() {
+            *slot = suffix[c as usize];
+            c = prefix[c as usize];
+        }
+        previous = Some(code);
+    }
+    return Ok(finish(out, len));
+}
+
+/// Bring decoded indices to the expected length, extra data being dropped and
+/// missing pixels being set to the first palette entry
+fn finish(mut out: Vec<u8>, len: usize) -> Vec<u8> {
+    out.resize(len, 0);
+    return out;
+}
@@ -0,0 +1,160 @@
+use crate::Color;
+
+/// Number of hue steps in a full turn of the colour wheel: 255 steps between
+/// each of red, yellow, green, cyan, blue and magenta, so that one step
+/// changes a bright saturated colour by exactly one unit. Hues are taken
+/// modulo this value.
+pub const HUE_STEPS: u16 = 6 * 255;
+
+/// Hue, saturation and value
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct Hsv {
+    pub h: u16,
+    pub s: u8,
+    pub v: u8,
+}
+
+/// Hue, saturation and lightness
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct Hsl {
+    pub h: u16,
+    pub s: u8,
+    pub l: u8,
+}
+
+/// `a * b / 255` rounded to the nearest integer
+fn scale(a: u32, b: u32) -> u32 {
+    return (a * b + 127) / 255;
+}
+
+impl From<Hsv> for Color {
+    fn from(hsv: Hsv) -> Self {
+        let h = hsv.h % HUE_STEPS;
+        let (sector, fract) = (h / 255, (h % 255) as u32);
+        let v = hsv.v as u32;
+        let p = v - scale(v, hsv.s as u32);
+        // Channels going up and down within the sector
+        let rising = (p + scale(v - p, fract)) as u8;
+        let falling = (v - scale(v - p, fract)) as u8;
+        let (v, p) = (v as u8, p as u8);
+        let (r, g, b) = match sector {
+            0 => (v, rising, p),
+            1 => (falling, v, p),
+            2 => (p, v, rising),
+            3 => (p, falling, v),
+            4 => (rising, p, v),
+            _ => (v, p, falling),
+        };
+        return Color { r, g, b };
+    }
+}
+
+impl From<Color> for Hsv {
+    fn from(color: Color) -> Self {
+        let (r, g, b) = (color.r as i32, color.g as i32, color.b as i32);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        if delta == 0 {
+            // Greys have no hue
+            return Hsv {
+                h: 0,
+                s: 0,
+                v: max as u8,
+            };
+        }
+        // Position within the sector, rounded to the nearest step
+        let offset = |up: i32, down: i32| ((up - down) * 510 + delta).div_euclid(2 * delta);
+        let h = if max == r {
+            offset(g, b)
+        } else if max == g {
+            510 + offset(b, r)
+        } else {
+            1020 + offset(r, g)
+        };
+        return Hsv {
+            h: h.rem_euclid(HUE_STEPS as i32) as u16,
+            s: ((delta * 255 + max / 2) / max) as u8,
+            v: max as u8,
+        };
+    }
+}
+
+/// Largest difference between the brightest and the darkest channel at
+/// lightness `l`, which is their average rounded up
+fn max_chroma(l: u32) -> u32 {
+    return (2 * l).min(511 - 2 * l);
+}
+
+impl From<Hsl> for Hsv {
+    fn from(hsl: Hsl) -> Self {
+        let l = hsl.l as u32;
+        let chroma = scale(hsl.s as u32, max_chroma(l));
+        // The brightest and the darkest channels add up to 2 * l, or to
+        // 2 * l - 1 when their average was rounded up, which only happens
+        // when they are an odd distance apart
+        let v = (2 * l - chroma % 2 + chroma) / 2;
+        let s = (chroma * 255 + v / 2).checked_div(v).unwrap_or(0);
+        return Hsv {
+            h: hsl.h,
+            s: s as u8,
+            v: v as u8,
+        };
+    }
+}
+
+impl From<Hsv> for Hsl {
+    fn from(hsv: Hsv) -> Self {
+        let v = hsv.v as u32;
+        // Difference between the brightest channel and the darkest one
+        let chroma = scale(v, hsv.s as u32);
+        let l = (2 * v - chroma).div_ceil(2);
+        let max = max_chroma(l);
+        let s = (chroma * 255 + max / 2).checked_div(max).unwrap_or(0);
+        return Hsl {
+            h: hsv.h,
+            s: s.min(255) as u8,
+            l: l as u8,
+        };
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        return Hsv::from(hsl).into();
+    }
+}
+
+impl From<Color> for Hsl {
+    fn from(color: Color) -> Self {
+        return Hsv::from(color).into();
+    }
+}
+
+impl Color {
+    /// Fully saturated and bright colour at `hue` on the colour wheel, 0 being
+    /// red, 510 green and 1020 blue
+    pub fn from_hue(hue: u16) -> Self {
+        return Hsv {
+            h: hue,
+            s: 255,
+            v: 255,
+        }
+        .into();
+    }
+
+    pub fn to_hsv(&self) -> Hsv {
+        return Hsv::from(*self);
+    }
+
+    pub fn to_hsl(&self) -> Hsl {
+        return Hsl::from(*self);
+    }
+
+    /// Turn the colour `steps` forward on the colour wheel, keeping its
+    /// saturation and value
+    pub fn rotate_hue(&self, steps: u16) -> Self {
+        let mut hsv = self.to_hsv();
+        hsv.h = (hsv.h % HUE_STEPS + steps % HUE_STEPS) % HUE_STEPS;
+        return hsv.into();
+    }
+}
@@ -2,7 +2,7 @@ use crate::gamma;
 use core::ops::Mul;
 
 #[repr(C)] // Force Rust to use a C compatible representation for Color
-#[derive(Copy, Clone, Default)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -13,6 +13,42 @@ impl Color {
     pub const RED: Color = Color { r: 255, g: 0, b: 0 };
     pub const GREEN: Color = Color { r: 0, g: 255, b: 0 };
     pub const BLUE: Color = Color { r: 0, g: 0, b: 255 };
+    pub const BLACK: Color = Color { r: 0, g: 0, b: 0 };
+    pub const WHITE: Color = Color {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+    pub const YELLOW: Color = Color {
+        r: 255,
+        g: 255,
+        b: 0,
+    };
+    pub const CYAN: Color = Color {
+        r: 0,
+        g: 255,
+        b: 255,
+    };
+    pub const MAGENTA: Color = Color {
+        r: 255,
+        g: 0,
+        b: 255,
+    };
+    pub const ORANGE: Color = Color {
+        r: 255,
+        g: 165,
+        b: 0,
+    };
+    pub const PURPLE: Color = Color {
+        r: 128,
+        g: 0,
+        b: 128,
+    };
+    pub const PINK: Color = Color {
+        r: 255,
+        g: 192,
+        b: 203,
+    };
 
     pub fn gamma_correct(&self) -> Self {
         Self {
@@ -21,6 +57,106 @@ impl Color {
             b: gamma::gamma_correct(self.b),
         }
     }
+
+    /// Scale every channel by `factor / 255`, rounding to the nearest value
+    pub fn scale(&self, factor: u8) -> Self {
+        return Color {
+            r: mul_255(self.r, factor),
+            g: mul_255(self.g, factor),
+            b: mul_255(self.b, factor),
+        };
+    }
+
+    /// Linear interpolation from `a` (`t` = 0) to `b` (`t` = 255), rounding
+    /// to the nearest value
+    pub fn lerp(a: Color, b: Color, t: u8) -> Self {
+        let mix = |a: u8, b: u8| {
+            let sum = a as u32 * (255 - t as u32) + b as u32 * t as u32;
+            ((sum + 127) / 255) as u8
+        };
+        return Color {
+            r: mix(a.r, b.r),
+            g: mix(a.g, b.g),
+            b: mix(a.b, b.b),
+        };
+    }
+
+    /// Mean of `colors`, rounding to the nearest value. Black if `colors` is empty.
+    pub fn average(colors: &[Color]) -> Self {
+        if colors.is_empty() {
+            return Color::default();
+        }
+        let (mut r, mut g, mut b) = (0u32, 0u32, 0u32);
+        for color in colors {
+            r += color.r as u32;
+            g += color.g as u32;
+            b += color.b as u32;
+        }
+        let n = colors.len() as u32;
+        return Color {
+            r: ((r + n / 2) / n) as u8,
+            g: ((g + n / 2) / n) as u8,
+            b: ((b + n / 2) / n) as u8,
+        };
+    }
+}
+
+/// `a * b / 255` rounded to the nearest value. As 255 is odd, a quotient is
+/// never exactly halfway and adding 127 before dividing is enough.
+fn mul_255(a: u8, b: u8) -> u8 {
+    return ((a as u32 * b as u32 + 127) / 255) as u8;
+}
+
+impl core::ops::Add for Color {
+    type Output = Self;
+
+    /// Saturating addition of every channel
+    fn add(self, other: Self) -> Self::Output {
+        return Color {
+            r: self.r.saturating_add(other.r),
+            g: self.g.saturating_add(other.g),
+            b: self.b.saturating_add(other.b),
+        };
+    }
+}
+
+impl core::ops::AddAssign for Color {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl core::ops::Sub for Color {
+    type Output = Self;
+
+    /// Saturating subtraction of every channel
+    fn sub(self, other: Self) -> Self::Output {
+        return Color {
+            r: self.r.saturating_sub(other.r),
+            g: self.g.saturating_sub(other.g),
+            b: self.b.saturating_sub(other.b),
+        };
+    }
+}
+
+impl core::ops::SubAssign for Color {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl core::ops::Mul<Color> for Color {
+    type Output = Self;
+
+    /// Modulate every channel by the matching one of `other`, white leaving
+    /// the colour unchanged
+    fn mul(self, other: Color) -> Self::Output {
+        return Color {
+            r: mul_255(self.r, other.r),
+            g: mul_255(self.g, other.g),
+            b: mul_255(self.b, other.b),
+        };
+    }
 }
 
 // In order to check the range
@@ -56,55 +192,170 @@ impl core::ops::Div<f32> for Color {
     }
 }
 
+/// An image of `W` columns and `H` rows. The defaults give the size of the
+/// panel, so that a plain `Image` is what the matrix shows; larger images can
+/// be used for chained panels or as off-screen buffers, of which a part is
+/// taken with `viewport()`.
 #[repr(transparent)] // to ensure that it keeps the same representation as its unique element.
-pub struct Image([Color; 64]);
+#[derive(Clone)]
+pub struct Image<const W: usize = 8, const H: usize = 8>([[Color; W]; H]);
+
+/// Image with the size of the 8x8 panel
+pub type PanelImage = Image<8, 8>;
 
-impl Image {
-    pub fn new_solid(color: Color) -> Self {
-        let image = [color; 64];
+impl<const W: usize, const H: usize> Image<W, H> {
+    pub const WIDTH: usize = W;
+    pub const HEIGHT: usize = H;
+
+    pub const fn new_solid(color: Color) -> Self {
+        let image = [[color; W]; H];
         return Image(image);
     }
 
+    /// Image of the given pixels, row by row
+    pub const fn from_rows(rows: [[Color; W]; H]) -> Self {
+        return Image(rows);
+    }
+
     pub fn row(&self, row: usize) -> &[Color] {
-        return &self.0[(8 * row)..(8 * (row + 1))];
+        return &self.0[row];
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [Color] {
+        return &mut self.0[row];
     }
 
     pub fn gradient(color: Color) -> Self {
-        let mut grad = Image::new_solid(color);
-        for i in 0..8 {
-            for j in 0..8 {
+        let mut grad = Self::new_solid(color);
+        for i in 0..H {
+            for j in 0..W {
                 // Each pixel should receive the reference color divided by (1 + row * row + col)
                 grad[(i, j)] = color / (1.0 + (i * i + j) as f32);
             }
         }
         return grad;
     }
+
+    /// The RGB bytes of every pixel, row by row
+    pub fn as_bytes(&self) -> &[u8] {
+        // Color is 3 bytes without padding thanks to repr(C), and nested
+        // arrays are contiguous
+        unsafe {
+            return core::slice::from_raw_parts(self as *const Self as *const u8, 3 * W * H);
+        }
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            return core::slice::from_raw_parts_mut(self as *mut Self as *mut u8, 3 * W * H);
+        }
+    }
+
+    /// Pixel at column `x` and row `y`, or `None` outside of the image. Note
+    /// that indexing takes (row, column) instead.
+    pub fn get(&self, x: usize, y: usize) -> Option<&Color> {
+        return self.0.get(y)?.get(x);
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Color> {
+        return self.0.get_mut(y)?.get_mut(x);
+    }
+
+    /// Every pixel with its (x, y) coordinates, row by row
+    pub fn pixels(&self) -> impl Iterator<Item = ((usize, usize), &Color)> {
+        return self.0.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, pixel)| ((x, y), pixel))
+        });
+    }
+
+    pub fn pixels_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut Color)> {
+        return self.0.iter_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut()
+                .enumerate()
+                .map(move |(x, pixel)| ((x, y), pixel))
+        });
+    }
+
+    /// Rows from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[Color]> {
+        return self.0.iter().map(|row| row.as_slice());
+    }
+
+    /// Copies of the columns from left to right, each from top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = [Color; H]> + '_ {
+        return (0..W).map(move |x| core::array::from_fn(|y| self.0[y][x]));
+    }
+
+    /// New image made of `f` applied to every pixel
+    pub fn map(&self, mut f: impl FnMut(Color) -> Color) -> Self {
+        let mut result = self.clone();
+        for (_, pixel) in result.pixels_mut() {
+            *pixel = f(*pixel);
+        }
+        return result;
+    }
+
+    /// New image made of `f` applied to every pixel of this image and the
+    /// one at the same place in `other`
+    pub fn zip_map(&self, other: &Self, mut f: impl FnMut(Color, Color) -> Color) -> Self {
+        let mut result = self.clone();
+        for ((x, y), pixel) in result.pixels_mut() {
+            *pixel = f(*pixel, other.0[y][x]);
+        }
+        return result;
+    }
+
+    /// Paint the `width` x `height` rectangle whose top left corner is at
+    /// column `x` and row `y`, clipped to the image
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: usize, height: usize, color: Color) {
+        let clip = |start: i32, len: usize, max: usize| {
+            let end = (start as i64 + len as i64).clamp(0, max as i64) as usize;
+            (start.clamp(0, max as i32) as usize)..end
+        };
+        for row in clip(y, height, H) {
+            self.0[row][clip(x, width, W)].fill(color);
+        }
+    }
+
+    /// The `VW` x `VH` part of this image whose top left corner is at column
+    /// `x` and row `y`. Pixels falling outside of this image are black.
+    pub fn viewport<const VW: usize, const VH: usize>(&self, x: i32, y: i32) -> Image<VW, VH> {
+        let mut view = Image::<VW, VH>::default();
+        for row in 0..VH {
+            for col in 0..VW {
+                let (src_row, src_col) = (y + row as i32, x + col as i32);
+                if (0..H as i32).contains(&src_row) && (0..W as i32).contains(&src_col) {
+                    view[(row, col)] = self[(src_row as usize, src_col as usize)];
+                }
+            }
+        }
+        return view;
+    }
 }
 
-impl Default for Image {
+impl<const W: usize, const H: usize> Default for Image<W, H> {
     fn default() -> Self {
         return Image::new_solid(Color::default());
     }
 }
 
-impl core::ops::Index<(usize, usize)> for Image {
+impl<const W: usize, const H: usize> core::ops::Index<(usize, usize)> for Image<W, H> {
     type Output = Color;
     fn index(&self, index: (usize, usize)) -> &Self::Output {
-        /* Since Image has 64 positions in one dimention,
-           we convert a ideal matrix ixj to an one dimention array by doing 8*i + j */
-        return &self.0[8 * index.0 + index.1];
+        // (row, column)
+        return &self.0[index.0][index.1];
     }
 }
 
-impl core::ops::IndexMut<(usize, usize)> for Image {
+impl<const W: usize, const H: usize> core::ops::IndexMut<(usize, usize)> for Image<W, H> {
     fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
-        /* Since Image has 64 positions in one dimention,
-           we convert a ideal matrix ixj to an one dimention array by doing 8*i + j */
-        return &mut self.0[8 * index.0 + index.1];
+        return &mut self.0[index.0][index.1];
     }
 }
 
-impl AsRef<[u8; 192]> for Image {
+impl AsRef<[u8; 192]> for PanelImage {
     fn as_ref(&self) -> &[u8; 192] {
         unsafe {
             return core::mem::transmute(self);
@@ -112,7 +363,7 @@ impl AsRef<[u8; 192]> for Image {
     }
 }
 
-impl AsMut<[u8; 192]> for Image {
+impl AsMut<[u8; 192]> for PanelImage {
     fn as_mut(&mut self) -> &mut [u8; 192] {
         unsafe {
             return core::mem::transmute(self);
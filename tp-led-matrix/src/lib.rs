@@ -1,6 +1,45 @@
 #![no_std]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod image;
+#[cfg(target_os = "none")]
 pub mod matrix;
-pub use image::{Color, Image};
+#[cfg(target_os = "none")]
+pub mod storage;
+pub use image::{Color, Image, PanelImage};
+/// Time of the monotonic timer in milliseconds, on 64 bits so that it never
+/// wraps around
+pub type Instant = fugit::TimerInstantU64<1000>;
+/// Time between two `Instant`s
+pub type Duration = fugit::TimerDurationU64<1000>;
 pub mod gamma;
+pub mod hsv;
+pub mod css;
 pub mod embedded;
+pub mod font;
+pub mod fonts;
+pub mod markup;
+pub mod blend;
+pub mod ppm;
+pub mod protocol;
+pub mod screensaver;
+pub mod scroller;
+pub mod transform;
+pub mod transition;
+pub mod palette;
+pub mod pattern;
+pub mod correction;
+pub mod spectrum;
+pub mod automaton;
+pub mod effect;
+pub mod playlist;
+pub mod sprite;
+pub mod tilemap;
+pub mod timeline;
+pub mod wav;
+pub mod animations;
+#[cfg(feature = "alloc")]
+pub mod scale;
+#[cfg(feature = "alloc")]
+pub mod gif;
@@ -1,20 +1,47 @@
 #![no_std]
 #![no_main]
 
+use core::fmt::Write;
 use core::mem::MaybeUninit;
 use defmt_rtt as _;
 use dwt_systick_monotonic::{DwtSystick, ExtU32};
 use heapless::pool::{Box, Node, Pool};
-use ibm437::IBM437_8X8_REGULAR;
+use heapless::String;
 use panic_probe as _;
 use stm32l4xx_hal::serial::{Config, Event, Rx, Serial};
 use stm32l4xx_hal::{pac::USART1, prelude::*};
+use tp_led_matrix::correction::{Correction, Profile};
+use tp_led_matrix::playlist::{Command, Playlist, Scheduler};
+use tp_led_matrix::animations::ANIMATIONS;
+use tp_led_matrix::protocol::{self, Receiver, MAX_COMMAND_LEN};
+use tp_led_matrix::screensaver::{Mode, Screensaver, PAUSE_MS, STEP_MS};
+use tp_led_matrix::storage::{self, Storage};
+use tp_led_matrix::transform::{Orientation, Rotation};
+use tp_led_matrix::transition::{Transition, Transitioner};
 use tp_led_matrix::{matrix::Matrix, Image};
 
-use embedded_graphics::{
-    mono_font::MonoTextStyleBuilder, pixelcolor::Rgb888, prelude::*, text::Text,
+/// How the panel is mounted on the board and how its colours are corrected,
+/// until a profile is set with a `profile` command and saved in flash
+const DEFAULT_PROFILE: Profile = Profile {
+    orientation: Orientation {
+        rotation: Rotation::Deg0,
+        mirrored: false,
+    },
+    correction: Correction::NEUTRAL,
 };
 
+/// Transition of the received images which do not announce one
+const DEFAULT_TRANSITION: Transition = Transition::CUT;
+
+/// What is shown when no image is received
+const SCREENSAVER_MODE: Mode = Mode::Text;
+
+/// Seed of the shuffled playlists and of the random effects
+const PLAYLIST_SEED: u32 = 202;
+
+/// Time between two refreshes of the whole panel
+const FRAME_US: u32 = 1_000_000 / 60;
+
 #[rtic::app(device = stm32l4xx_hal::pac, dispatchers = [USART2, USART3])]
 mod app {
     use super::*;
@@ -23,11 +50,19 @@ mod app {
     type MyMonotonic = DwtSystick<80_000_000>;
     type Instant = <MyMonotonic as rtic::Monotonic>::Instant;
 
+    /// `at` in the milliseconds used by the library
+    fn millis(at: Instant) -> tp_led_matrix::Instant {
+        return tp_led_matrix::Instant::from_ticks(at.duration_since_epoch().to_millis());
+    }
+
     #[shared]
     struct Shared {
-        next_image: Option<Box<Image>>,
+        next_image: Option<(Box<Image>, Transition)>,
+        /// Profile to apply to the matrix at the end of the current refresh
+        next_profile: Option<Profile>,
         pool: Pool<Image>,
         changes: u32,
+        scheduler: Scheduler<'static>,
     }
 
     #[local]
@@ -36,6 +71,8 @@ mod app {
         usart1_rx: Rx<USART1>,
         current_image: Box<Image>,
         rx_image: Box<Image>,
+        storage: Storage,
+        profile: Profile,
     }
 
     #[init]
@@ -56,10 +93,19 @@ mod app {
         // Setup the clocks at 80MHz using HSI (by default since HSE/MSI are not configured).
         // The flash wait states will be configured accordingly.
         let clocks = rcc.cfgr.sysclk(80.MHz()).freeze(&mut flash.acr, &mut pwr);
+
+        // The playlist saved before the last reset, if any
+        let storage = Storage::new(flash.keyr, flash.sr, flash.cr);
+        let playlist: Playlist = match storage.load().map(str::parse) {
+            Some(Ok(playlist)) => playlist,
+            _ => Playlist::new(),
+        };
+        let scheduler = Scheduler::new(playlist, &ANIMATIONS, PLAYLIST_SEED);
+        let profile = storage.load_profile().unwrap_or(DEFAULT_PROFILE);
         let mut gpioa = dp.GPIOA.split(&mut rcc.ahb2);
         let mut gpiob = dp.GPIOB.split(&mut rcc.ahb2);
         let mut gpioc = dp.GPIOC.split(&mut rcc.ahb2);
-        let matrix = Matrix::new(
+        let mut matrix = Matrix::new(
             gpioa.pa2,
             gpioa.pa3,
             gpioa.pa4,
@@ -81,6 +127,7 @@ mod app {
             &mut gpioc.otyper,
             clocks,
         );
+        matrix.set_profile(&profile);
 
         // Serial port
         let tx_pin =
@@ -128,14 +175,18 @@ mod app {
         return (
             Shared {
                 next_image,
+                next_profile: None,
                 pool,
                 changes,
+                scheduler,
             },
             Local {
                 matrix,
                 usart1_rx,
                 current_image,
                 rx_image,
+                storage,
+                profile,
             },
             init::Monotonics(mono),
         );
@@ -146,22 +197,28 @@ mod app {
         loop {}
     }
 
-    #[task(local = [matrix, next_row: usize = 0, current_image], shared = [&pool, next_image], priority = 2)]
+    #[task(local = [matrix, next_row: usize = 0, current_image, transitioner: Transitioner = Transitioner::new()], shared = [&pool, next_image, next_profile], priority = 2)]
     fn display(mut cx: display::Context, at: Instant) {
-        cx.local.matrix.send_row(
-            *cx.local.next_row,
-            cx.local.current_image.row(*cx.local.next_row),
-        );
+        let transitioner = cx.local.transitioner;
+        // While a transition is under way, the mix of the old and new images
+        // is shown instead of the new one
+        let shown = transitioner.image().unwrap_or(cx.local.current_image);
+        cx.local.matrix.send_image_row(*cx.local.next_row, shown);
 
         if *cx.local.next_row as usize == 7 {
             cx.shared.next_image.lock(|next_image| {
                 if next_image.is_none() == false {
-                    if let Some(mut image) = next_image.take() {
+                    if let Some((mut image, transition)) = next_image.take() {
+                        transitioner.start(transition, cx.local.current_image);
                         core::mem::swap(&mut image, cx.local.current_image.into());
                         cx.shared.pool.free(image);
                     }
                 }
-            })
+            });
+            if let Some(profile) = cx.shared.next_profile.lock(Option::take) {
+                cx.local.matrix.set_profile(&profile);
+            }
+            transitioner.advance(FRAME_US, cx.local.current_image);
         }
 
         // Increment next_row up to 7 and wraparound to 0
@@ -172,10 +229,8 @@ mod app {
         display::spawn_at(next, next).unwrap();
     }
 
-    #[task(binds = USART1, local = [usart1_rx, next_pos: usize = 0, rx_image], shared = [next_image, &pool], priority = 2)]
+    #[task(binds = USART1, local = [usart1_rx, receiver: Receiver = Receiver::new(), rx_image], shared = [next_image, &pool], priority = 2)]
     fn receive_byte(mut cx: receive_byte::Context) {
-        let next_pos: &mut usize = cx.local.next_pos;
-
         if let Ok(b) = cx.local.usart1_rx.read() {
             let error = cx.local.usart1_rx.check_for_error();
             match error {
@@ -186,41 +241,88 @@ mod app {
                     return;
                 }
             }
-            if b == 0xff {
-                *next_pos = 0;
-            } else if *next_pos == usize::MAX {
-                // blocking untill arriving of a new image
-            } else if let Some(image) = cx.local.rx_image.into() {
-                image.as_mut()[*next_pos] = b;
-                *next_pos += 1;
-            } else {
-                defmt::error!("Error while reading incoming data");
-            }
+
+            let event = cx.local.receiver.receive(b, cx.local.rx_image);
 
             // If the received image is complete, make it available to
             // the display task.
-            if *next_pos == 8 * 8 * 3 {
+            if event == Some(protocol::Event::Complete) {
                 cx.shared.next_image.lock(|next_image| {
                     if next_image.is_none() != false {
-                        if let Some(image) = next_image.take() {
+                        if let Some((image, _)) = next_image.take() {
                             cx.shared.pool.free(image);
                         }
                     }
                     // Replace the image content by the new one, for example
-                    // by swapping them, and reset next_pos
+                    // by swapping them
                     let future_image = cx.shared.pool.alloc();
                     if future_image.is_some() {
                         let mut future_image = future_image.unwrap().init(Image::default());
                         core::mem::swap(&mut future_image, &mut cx.local.rx_image);
-                        *next_image = Some(future_image);
+                        let transition = cx.local.receiver.transition();
+                        *next_image =
+                            Some((future_image, transition.unwrap_or(DEFAULT_TRANSITION)));
                     }
                     notice_change::spawn().unwrap();
                 });
-                *next_pos = usize::MAX;
+            }
+
+            if event == Some(protocol::Event::Command) {
+                let mut line = String::new();
+                if let Some(command) = cx.local.receiver.command() {
+                    // Cannot fail, both have the same capacity
+                    let _ = line.push_str(command);
+                }
+                if command::spawn(line).is_err() {
+                    defmt::warn!("command dropped, the previous one is still running");
+                }
             }
         }
     }
 
+    /// Carry out a command received on the serial line, saving the playlist
+    /// or the profile if it changed
+    #[task(local = [storage, profile], shared = [scheduler, next_profile], priority = 1)]
+    fn command(mut cx: command::Context, line: String<MAX_COMMAND_LEN>) {
+        let command: Command = match line.parse() {
+            Ok(command) => command,
+            Err(_) => {
+                defmt::warn!("invalid command: {}", line.as_str());
+                return;
+            }
+        };
+        if let Command::Profile(setting) = &command {
+            cx.local.profile.apply(setting);
+            let profile = *cx.local.profile;
+            cx.shared
+                .next_profile
+                .lock(|next_profile| *next_profile = Some(profile));
+            if cx.local.storage.save_profile(&profile).is_err() {
+                defmt::warn!("the profile could not be saved");
+            }
+            return;
+        }
+        let now = millis(monotonics::now());
+        let mut text: String<{ storage::CAPACITY }> = String::new();
+        let changed = cx.shared.scheduler.lock(|scheduler| {
+            let changed = scheduler.apply(&command, now);
+            if changed == Ok(true) {
+                // Always fits, a full playlist taking less than 1800 bytes
+                let _ = write!(text, "{}", scheduler.playlist());
+            }
+            return changed;
+        });
+        match changed {
+            Ok(true) => {
+                if cx.local.storage.save(&text).is_err() {
+                    defmt::warn!("the playlist could not be saved");
+                }
+            }
+            Ok(false) => {}
+            Err(_) => defmt::warn!("command failed: {}", line.as_str()),
+        }
+    }
+
     #[task(shared = [changes], priority = 1)]
     fn notice_change(mut cx: notice_change::Context) {
         cx.shared
@@ -231,16 +333,9 @@ mod app {
             })
     }
 
-    #[task(local = [last_changes: u32 = 0, color_index: u8 = 0, offset: i32 = 10], shared = [next_image, &pool, changes], priority = 1)]
+    #[task(local = [screensaver: Screensaver = Screensaver::with_mode(SCREENSAVER_MODE)], shared = [next_image, &pool, changes, scheduler], priority = 1)]
     fn screensaver(mut cx: screensaver::Context, at: Instant) {
-        let last_changes: &mut u32 = cx.local.last_changes;
-        let color_index: &mut u8 = cx.local.color_index as &mut u8;
-        let offset: &mut i32 = cx.local.offset as &mut i32;
-        // let text = "Hello SE202";
-        let text = "This Rust SE202 project will get me a good grade?";
-        let text_size = text.len() as i32;
-        let offset_max: i32 = 8; // offset based on the time of one letter
-        let offset_min: i32 = -1 * offset_max * text_size; // generic code based on the display time of each letter
+        let screensaver: &mut Screensaver = cx.local.screensaver;
         let mut changes = 0;
         let next;
 
@@ -248,53 +343,38 @@ mod app {
             changes = *changes_;
         });
 
-        if *last_changes == changes {
+        if screensaver.is_idle(changes) {
+            // The playlist if there is one, the screensaver otherwise
             let mut image_aux = Image::default();
-
-            // Selecting color based on the index
-            let color_now = match *color_index {
-                0 => Rgb888::RED,
-                1 => Rgb888::GREEN,
-                2 => Rgb888::BLUE,
-                _ => unreachable!(),
+            let transition = cx
+                .shared
+                .scheduler
+                .lock(|scheduler| scheduler.render(millis(at), &mut image_aux));
+            let transition = match transition {
+                Some(transition) => transition,
+                None => {
+                    image_aux = screensaver.render();
+                    Transition::CUT
+                }
             };
 
-            // Create a new text style
-            let text_style = MonoTextStyleBuilder::new()
-                .font(&IBM437_8X8_REGULAR)
-                .text_color(color_now)
-                .background_color(Rgb888::BLACK)
-                .build();
-
-            // Create a new text object
-            let text = Text::new(text, Point::new(*offset, 6), text_style);
-
-            // Draw the text onto the image
-            let _text = text.draw(&mut image_aux);
-
             let image = cx.shared.pool.alloc();
             if image.is_some() {
                 let image = image.unwrap().init(image_aux);
 
                 // Returning the previous next_image to the pool
                 cx.shared.next_image.lock(|next_image| {
-                    if let Some(image) = next_image.take() {
+                    if let Some((image, _)) = next_image.take() {
                         cx.shared.pool.free(image);
                     }
-                    *next_image = Some(image); // getting next image
+                    *next_image = Some((image, transition)); // getting next image
                 });
 
-                *offset = *offset - 1;
-                if *offset == offset_min {
-                    *offset = offset_max; // reseting offset
-                    *color_index = (*color_index + 1) % 3;
-                }
+                screensaver.advance();
             }
-            next = at + 60.millis(); // if no received byte, it gets called every 60ms
+            next = at + STEP_MS.millis(); // if no received byte, it gets called every 60ms
         } else {
-            *offset = offset_max; // reseting offset
-            *last_changes = changes; // record the current changes into last_changes
-            next = at + 1.secs(); // wait 1 second after last received byte to restart the screensaver (better for the eyes)
+            next = at + PAUSE_MS.millis(); // wait 1 second after last received byte to restart the screensaver (better for the eyes)
         }
         screensaver::spawn_at(next, next).unwrap();
     }
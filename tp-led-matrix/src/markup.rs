@@ -0,0 +1,181 @@
+use crate::Color;
+use core::str::FromStr;
+
+/// Spans can be nested this deep
+pub const MAX_DEPTH: usize = 8;
+
+/// Why some text is not valid markup
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A `{` without its `}`
+    UnclosedTag,
+    /// A tag which is neither a style, a colour nor an icon
+    UnknownTag,
+    /// A `{/}` while no span is open
+    NothingToClose,
+    /// More than `MAX_DEPTH` spans open at once
+    TooDeep,
+}
+
+/// How a span of text is drawn. `None` and `false` leave the choice to
+/// whatever draws the text.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub blink: bool,
+    /// Every column in another colour, taking precedence over `color`
+    pub rainbow: bool,
+}
+
+/// Piece of text drawn with the same style
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub style: Style,
+}
+
+/// Icons which can be put in the text, such as `{heart}`
+const ICONS: [(&str, &str); 12] = [
+    ("heart", "\u{e000}"),
+    ("star", "\u{e001}"),
+    ("smiley", "\u{e002}"),
+    ("note", "\u{e003}"),
+    ("sun", "\u{e004}"),
+    ("check", "\u{e005}"),
+    ("cross", "\u{e006}"),
+    ("warning", "\u{e007}"),
+    ("up", "\u{e008}"),
+    ("down", "\u{e009}"),
+    ("left", "\u{e00a}"),
+    ("right", "\u{e00b}"),
+];
+
+/// Text with inline tags between braces:
+///
+/// - `{red}`, `{#ff8000}`, `{hsv(30, 100, 100)}`: text colour, as understood
+///   by `Color::from_str`
+/// - `{bg:navy}`: background colour
+/// - `{blink}`, `{rainbow}`: blinking or multicoloured text
+/// - `{/}`: back to the style before the last tag above
+/// - `{heart}`, `{star}`, `{smiley}`, `{note}`, `{sun}`, `{check}`,
+///   `{cross}`, `{warning}`, `{up}`, `{down}`, `{left}`, `{right}`: icons
+/// - `{{`: a literal `{`
+///
+/// For example `{red}ALERT{/} doors close {blink}now{/}`. Spans still open
+/// at the end of the text are closed silently. Nothing is allocated: the
+/// spans are found again every time they are iterated over.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Markup<'a>(&'a str);
+
+impl<'a> Markup<'a> {
+    /// Check that the whole of `source` is valid markup
+    pub fn new(source: &'a str) -> Result<Self, Error> {
+        let markup = Markup(source);
+        for span in markup.parse() {
+            span?;
+        }
+        return Ok(markup);
+    }
+
+    /// `source` taken as markup without checking it. Iterating over its
+    /// spans stops at the first error.
+    pub const fn new_unchecked(source: &'a str) -> Self {
+        return Markup(source);
+    }
+
+    pub fn source(&self) -> &'a str {
+        return self.0;
+    }
+
+    /// Pieces of text in order, icons being spans of their own
+    pub fn spans(&self) -> impl Iterator<Item = Span<'a>> {
+        return self.parse().map_while(Result::ok);
+    }
+
+    fn parse(&self) -> Parser<'a> {
+        return Parser {
+            rest: self.0,
+            styles: [Style::default(); MAX_DEPTH + 1],
+            depth: 0,
+        };
+    }
+}
+
+/// Iterator over the spans of some markup, keeping the styles of the open
+/// spans on a stack
+struct Parser<'a> {
+    rest: &'a str,
+    styles: [Style; MAX_DEPTH + 1],
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Apply the tag `name`, returning the text of an icon
+    fn tag(&mut self, name: &str) -> Result<Option<&'static str>, Error> {
+        if name == "/" {
+            if self.depth == 0 {
+                return Err(Error::NothingToClose);
+            }
+            self.depth -= 1;
+            return Ok(None);
+        }
+        if let Some(&(_, icon)) = ICONS.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)) {
+            return Ok(Some(icon));
+        }
+        let mut style = self.styles[self.depth];
+        if name.eq_ignore_ascii_case("blink") {
+            style.blink = true;
+        } else if name.eq_ignore_ascii_case("rainbow") {
+            style.rainbow = true;
+        } else if let Some(color) = name.strip_prefix("bg:") {
+            style.background = Some(Color::from_str(color).map_err(|_| Error::UnknownTag)?);
+        } else {
+            style.color = Some(Color::from_str(name).map_err(|_| Error::UnknownTag)?);
+            style.rainbow = false;
+        }
+        if self.depth == MAX_DEPTH {
+            return Err(Error::TooDeep);
+        }
+        self.depth += 1;
+        self.styles[self.depth] = style;
+        return Ok(None);
+    }
+}
+
+impl<'a> Iterator for Parser<'a> {
+    type Item = Result<Span<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let style = self.styles[self.depth];
+            if self.rest.is_empty() {
+                return None;
+            }
+            if let Some(rest) = self.rest.strip_prefix("{{") {
+                let text = &self.rest[..1];
+                self.rest = rest;
+                return Some(Ok(Span { text, style }));
+            }
+            if let Some(tag) = self.rest.strip_prefix('{') {
+                let Some(end) = tag.find('}') else {
+                    self.rest = "";
+                    return Some(Err(Error::UnclosedTag));
+                };
+                self.rest = &tag[end + 1..];
+                match self.tag(&tag[..end]) {
+                    Ok(Some(icon)) => return Some(Ok(Span { text: icon, style })),
+                    Ok(None) => continue,
+                    Err(error) => {
+                        self.rest = "";
+                        return Some(Err(error));
+                    }
+                }
+            }
+            let end = self.rest.find('{').unwrap_or(self.rest.len());
+            let (text, rest) = self.rest.split_at(end);
+            self.rest = rest;
+            return Some(Ok(Span { text, style }));
+        }
+    }
+}
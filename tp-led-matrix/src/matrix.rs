@@ -1,4 +1,6 @@
-use crate::{Color, Image};
+use crate::correction::{Correction, Corrector, Profile};
+use crate::transform::Orientation;
+use crate::{Color, PanelImage};
 use cortex_m::prelude::_embedded_hal_blocking_delay_DelayMs;
 use stm32l4xx_hal::gpio::Speed::VeryHigh;
 use stm32l4xx_hal::{gpio::*, rcc::Clocks};
@@ -34,6 +36,8 @@ pub struct Matrix {
     c5: PA5<Output<PushPull>>,
     c6: PB0<Output<PushPull>>,
     c7: PA3<Output<PushPull>>,
+    orientation: Orientation,
+    corrector: Corrector,
 }
 
 impl Matrix {
@@ -107,6 +111,8 @@ impl Matrix {
             c7: pa3
                 .into_push_pull_output_in_state(gpioa_moder, gpioa_otyper, PinState::Low)
                 .set_speed(VeryHigh),
+            orientation: Orientation::NORMAL,
+            corrector: Corrector::default(),
         };
 
         // Attend au moins 100ms que le DM163 soit initialisé, puis passe RST à l'état haut.
@@ -168,15 +174,15 @@ impl Matrix {
         self.row(7, PinState::Low);
     }
 
-    /// Send a full row of bytes in BGR order and pulse LAT low. Gamma correction
-    /// must be applied to every pixel before sending them. The previous row must
-    /// be deactivated and the new one activated.
+    /// Send a full row of bytes in BGR order and pulse LAT low. Colour and gamma
+    /// correction must be applied to every pixel before sending them. The previous
+    /// row must be deactivated and the new one activated.
     pub fn send_row(&mut self, row: usize, pixels: &[Color]) {
         let mut i = 0;
         let mut color_aux;
 
         for pixel in  pixels.iter().rev() {
-            color_aux = pixel.gamma_correct();
+            color_aux = self.corrector.apply(*pixel).gamma_correct();
 
             self.send_byte(color_aux.b);
             if i == 4 {
@@ -191,6 +197,29 @@ impl Matrix {
         self.row(row, PinState::High);
     }
 
+    /// Set how the panel is mounted. Images given to `send_image_row()` and
+    /// `display_image()` are turned accordingly while being sent.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Set the colour correction applied before gamma correction
+    pub fn set_correction(&mut self, correction: &Correction) {
+        self.corrector = Corrector::new(correction);
+    }
+
+    /// Apply the orientation and colour correction of `profile`
+    pub fn set_profile(&mut self, profile: &Profile) {
+        self.set_orientation(profile.orientation);
+        self.set_correction(&profile.correction);
+    }
+
+    /// Send row `row` of `image`, as seen with the panel orientation
+    pub fn send_image_row(&mut self, row: usize, image: &PanelImage) {
+        let pixels = self.orientation.row(image, row);
+        self.send_row(row, &pixels);
+    }
+
     /// Initialize bank0 by temporarily setting SB to low and sending 144 one bits,
     /// pulsing SCK high after each bit and pulsing LAT low at the end. SB is then
     /// restored to high.
@@ -207,11 +236,9 @@ impl Matrix {
     }
 
     /// Display a full image, row by row, as fast as possible.
-    pub fn display_image(&mut self, image: &Image) {
-        // Do not forget that image.row(n) gives access to the content of row n,
-        // and that self.send_row() uses the same format.
+    pub fn display_image(&mut self, image: &PanelImage) {
         for i in 0..8 {
-            self.send_row(i, image.row(i));
+            self.send_image_row(i, image);
         }
     }
 }
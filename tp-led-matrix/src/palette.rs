@@ -0,0 +1,147 @@
+use crate::{Color, Image};
+use core::ops::Range;
+
+/// Returned when an image has more colours than a palette can hold
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TooManyColors;
+
+/// A panel image stored as a palette of up to `N` colours and one index per
+/// pixel, `BYTES` being the size of the indices: 32 bytes of 4-bit indices
+/// for up to 16 colours, or 64 bytes of 8-bit indices for up to 64, all an
+/// 8x8 image can show. It takes `3 * N + 2 + BYTES` bytes, which is less than
+/// the 192 bytes of an `Image` with 4-bit indices, but with 8-bit indices
+/// only up to 41 colours. Use the `PalettedImage16` and `PalettedImage32`
+/// aliases rather than choosing the parameters by hand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PalettedImage<const N: usize, const BYTES: usize> {
+    palette: [Color; N],
+    colors: u16,
+    indices: [u8; BYTES],
+}
+
+/// 16 colours and 4-bit indices, 82 bytes instead of 192
+pub type PalettedImage16 = PalettedImage<16, 32>;
+/// 32 colours and 8-bit indices, 162 bytes instead of 192
+pub type PalettedImage32 = PalettedImage<32, 64>;
+
+const _: () = assert!(core::mem::size_of::<PalettedImage32>() < core::mem::size_of::<Image>());
+
+impl<const N: usize, const BYTES: usize> PalettedImage<N, BYTES> {
+    // Evaluated when the type is used, rejecting other parameters at compile time
+    const VALID: () = assert!((N <= 16 && BYTES == 32) || (N <= 64 && BYTES == 64));
+
+    /// Image whose pixels all use the first entry of `palette`. Panics if
+    /// `palette` is empty or longer than `N`.
+    pub fn new(palette: &[Color]) -> Self {
+        let () = Self::VALID;
+        assert!(!palette.is_empty() && palette.len() <= N);
+        let mut image = PalettedImage {
+            palette: [Color::default(); N],
+            colors: palette.len() as u16,
+            indices: [0; BYTES],
+        };
+        image.palette[..palette.len()].copy_from_slice(palette);
+        return image;
+    }
+
+    pub fn new_solid(color: Color) -> Self {
+        return PalettedImage::new(&[color]);
+    }
+
+    /// Convert `image` without loss, its colours being put in the palette in
+    /// the order in which they appear
+    pub fn from_image(image: &Image) -> Result<Self, TooManyColors> {
+        let mut paletted = PalettedImage::new_solid(image[(0, 0)]);
+        for ((x, y), &color) in image.pixels() {
+            let index = match paletted.palette().iter().position(|&c| c == color) {
+                Some(index) => index,
+                None => paletted.push_color(color).ok_or(TooManyColors)?,
+            };
+            paletted.set_index(x, y, index as u8);
+        }
+        return Ok(paletted);
+    }
+
+    pub fn to_image(&self) -> Image {
+        let mut image = Image::default();
+        for ((x, y), pixel) in image.pixels_mut() {
+            *pixel = self.get(x, y);
+        }
+        return image;
+    }
+
+    /// Colours in use
+    pub fn palette(&self) -> &[Color] {
+        return &self.palette[..self.colors as usize];
+    }
+
+    /// Changing an entry changes every pixel using it
+    pub fn palette_mut(&mut self) -> &mut [Color] {
+        return &mut self.palette[..self.colors as usize];
+    }
+
+    /// Add `color` at the end of the palette and return its index, or `None`
+    /// if the palette is full
+    pub fn push_color(&mut self, color: Color) -> Option<usize> {
+        let index = self.colors as usize;
+        if index == N {
+            return None;
+        }
+        self.palette[index] = color;
+        self.colors += 1;
+        return Some(index);
+    }
+
+    /// Palette index of the pixel at column `x` and row `y`
+    pub fn index(&self, x: usize, y: usize) -> u8 {
+        let i = 8 * y + x;
+        if BYTES == 32 {
+            return (self.indices[i / 2] >> (4 * (i % 2))) & 0x0f;
+        }
+        return self.indices[i];
+    }
+
+    /// Make the pixel at column `x` and row `y` use palette entry `index`.
+    /// Panics if `index` is not in the palette.
+    pub fn set_index(&mut self, x: usize, y: usize, index: u8) {
+        assert!((index as usize) < self.colors as usize);
+        let i = 8 * y + x;
+        if BYTES == 32 {
+            let shift = 4 * (i % 2);
+            self.indices[i / 2] = (self.indices[i / 2] & !(0x0f << shift)) | (index << shift);
+        } else {
+            self.indices[i] = index;
+        }
+    }
+
+    /// Colour of the pixel at column `x` and row `y`
+    pub fn get(&self, x: usize, y: usize) -> Color {
+        return self.palette[self.index(x, y) as usize];
+    }
+
+    /// Rotate the palette entries in `range` by `steps` places, entry `i`
+    /// taking the colour of entry `i - steps`. Pixels using these entries
+    /// appear to move along them without being touched, as in the classic
+    /// colour cycling effects.
+    pub fn cycle_palette(&mut self, range: Range<usize>, steps: usize) {
+        let entries = &mut self.palette_mut()[range];
+        if !entries.is_empty() {
+            let len = entries.len();
+            entries.rotate_right(steps % len);
+        }
+    }
+}
+
+impl<const N: usize, const BYTES: usize> TryFrom<&Image> for PalettedImage<N, BYTES> {
+    type Error = TooManyColors;
+
+    fn try_from(image: &Image) -> Result<Self, Self::Error> {
+        return PalettedImage::from_image(image);
+    }
+}
+
+impl<const N: usize, const BYTES: usize> From<&PalettedImage<N, BYTES>> for Image {
+    fn from(image: &PalettedImage<N, BYTES>) -> Self {
+        return image.to_image();
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{Color, Image};
+use core::f32::consts::PI;
+use micromath::F32Ext;
+
+/// A colour at a position along a gradient, from 0.0 to 1.0
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Stop {
+    pub position: f32,
+    pub color: Color,
+}
+
+impl Stop {
+    pub const fn new(position: f32, color: Color) -> Self {
+        return Stop { position, color };
+    }
+}
+
+/// Colour at `t` along `stops`, which must be sorted by position. Before the
+/// first stop and after the last one the colour stays the same.
+pub fn color_at(stops: &[Stop], t: f32) -> Color {
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return Color::BLACK;
+    };
+    if t <= first.position {
+        return first.color;
+    }
+    for pair in stops.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if t <= b.position {
+            let span = b.position - a.position;
+            if span <= 0.0 {
+                return b.color;
+            }
+            let mix = ((t - a.position) / span * 255.0).round() as u8;
+            return Color::lerp(a.color, b.color, mix);
+        }
+    }
+    return last.color;
+}
+
+/// Something to paint an image with, as a test pattern or a background.
+/// Positions are in pixels, (0.0, 0.0) being the centre of the top left
+/// pixel, and angles are in degrees, clockwise from the x axis going right.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Pattern<'a> {
+    /// Colours following `stops` in the direction of `angle`, the first
+    /// and last stops reaching the corners of the image
+    Linear { stops: &'a [Stop], angle: f32 },
+    /// Circles around `center`, `stops` going from the centre to `radius`
+    Radial {
+        stops: &'a [Stop],
+        center: (f32, f32),
+        radius: f32,
+    },
+    /// Sweep around `center`, `stops` making a full clockwise turn from
+    /// `angle`
+    Conic {
+        stops: &'a [Stop],
+        center: (f32, f32),
+        angle: f32,
+    },
+    /// Squares of `size` pixels, starting with the first colour at the top left
+    Checkerboard { colors: [Color; 2], size: usize },
+    /// Bands `width` pixels wide across the direction of `angle`, going
+    /// through `colors` in turn
+    Stripes {
+        colors: &'a [Color],
+        width: f32,
+        angle: f32,
+    },
+    /// Smooth value noise with features about `scale` pixels wide, mapped
+    /// through `stops`. The same `seed` always gives the same image.
+    Noise {
+        stops: &'a [Stop],
+        scale: f32,
+        seed: u32,
+    },
+}
+
+impl Pattern<'_> {
+    /// Colour of the pixel at column `x` and row `y` of a `width` x `height`
+    /// image
+    pub fn color(&self, x: usize, y: usize, width: usize, height: usize) -> Color {
+        let (px, py) = (x as f32, y as f32);
+        return match *self {
+            Pattern::Linear { stops, angle } => {
+                let (dx, dy) = direction(angle);
+                let (cx, cy) = ((width as f32 - 1.0) / 2.0, (height as f32 - 1.0) / 2.0);
+                // Distance from the centre to the corners along the direction
+                let extent = cx * dx.abs() + cy * dy.abs();
+                let along = (px - cx) * dx + (py - cy) * dy;
+                let t = if extent > 0.0 {
+                    0.5 + along / (2.0 * extent)
+                } else {
+                    0.0
+                };
+                color_at(stops, t)
+            }
+            Pattern::Radial {
+                stops,
+                center,
+                radius,
+            } => {
+                let distance = (px - center.0).hypot(py - center.1);
+                color_at(stops, distance / radius)
+            }
+            Pattern::Conic {
+                stops,
+                center,
+                angle,
+            } => {
+                let degrees = (py - center.1).atan2(px - center.0) * 180.0 / PI - angle;
+                color_at(stops, degrees.rem_euclid(360.0) / 360.0)
+            }
+            Pattern::Checkerboard { colors, size } => {
+                let size = size.max(1);
+                colors[(x / size + y / size) % 2]
+            }
+            Pattern::Stripes {
+                colors,
+                width,
+                angle,
+            } => {
+                if colors.is_empty() {
+                    return Color::BLACK;
+                }
+                let (dx, dy) = direction(angle);
+                let band = ((px * dx + py * dy) / width).floor() as i32;
+                colors[band.rem_euclid(colors.len() as i32) as usize]
+            }
+            Pattern::Noise { stops, scale, seed } => {
+                color_at(stops, value_noise(px / scale, py / scale, seed))
+            }
+        };
+    }
+
+    /// A whole image painted with this pattern
+    pub fn render<const W: usize, const H: usize>(&self) -> Image<W, H> {
+        let mut image = Image::default();
+        image.fill_pattern(self);
+        return image;
+    }
+}
+
+impl<const W: usize, const H: usize> Image<W, H> {
+    pub fn fill_pattern(&mut self, pattern: &Pattern) {
+        for ((x, y), pixel) in self.pixels_mut() {
+            *pixel = pattern.color(x, y, W, H);
+        }
+    }
+}
+
+/// Unit vector pointing at `angle` degrees
+fn direction(angle: f32) -> (f32, f32) {
+    let radians = angle * PI / 180.0;
+    return (radians.cos(), radians.sin());
+}
+
+/// Pseudo-random value between 0.0 and 1.0 attached to a lattice point
+fn lattice(x: i32, y: i32, seed: u32) -> f32 {
+    let mut h = seed ^ (x as u32).wrapping_mul(0x9e37_79b1) ^ (y as u32).wrapping_mul(0x85eb_ca77);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb_352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846c_a68b);
+    h ^= h >> 16;
+    return (h >> 8) as f32 / (1 << 24) as f32;
+}
+
+/// Lattice values smoothly interpolated in between
+fn value_noise(x: f32, y: f32, seed: u32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+    let (tx, ty) = (smooth(x - x0), smooth(y - y0));
+    let (x0, y0) = (x0 as i32, y0 as i32);
+    let top = lattice(x0, y0, seed) * (1.0 - tx) + lattice(x0 + 1, y0, seed) * tx;
+    let bottom = lattice(x0, y0 + 1, seed) * (1.0 - tx) + lattice(x0 + 1, y0 + 1, seed) * tx;
+    return top * (1.0 - ty) + bottom * ty;
+}
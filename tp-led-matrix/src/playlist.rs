@@ -0,0 +1,617 @@
+use crate::automaton::{Automaton, Rule};
+use crate::correction::Setting;
+use crate::effect::{
+    millis_between, Breathing, ColorWash, Effect, Fire, Plasma, Rain, Rng, Sparkle, Starfield,
+};
+use crate::font::Typeface;
+use crate::fonts::FONT_6X8;
+use crate::markup::Markup;
+use crate::protocol::Frame;
+use crate::screensaver::STEP_MS;
+use crate::scroller::TextScroller;
+use crate::transition::Transition;
+use crate::{Color, Image, Instant};
+use core::fmt::{self, Write};
+use core::str::FromStr;
+use heapless::{String, Vec};
+
+/// Entries a playlist holds at most
+pub const MAX_ENTRIES: usize = 16;
+/// Longest text of an entry, in bytes
+pub const MAX_TEXT_LEN: usize = 64;
+/// Time counted as one repetition of an effect, which has no end of its own
+pub const CYCLE_MS: u32 = 10_000;
+
+const DAY_MS: u32 = 24 * 3600 * 1000;
+
+/// Milliseconds of the monotonic timer since the last multiple of a day,
+/// reduced before narrowing so that days of uptime are not lost
+fn time_of_day(t: Instant) -> u32 {
+    return (t.ticks() % DAY_MS as u64) as u32;
+}
+
+/// Why a command is not valid or cannot be carried out
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    UnknownCommand,
+    /// Missing or unexpected words after the command
+    BadArguments,
+    /// Not `<n>s`, `<n>ms` or `x<n>`
+    BadLength,
+    UnknownContent,
+    UnknownEffect,
+    /// Longer than `MAX_TEXT_LEN` or not valid markup
+    BadText,
+    /// No entry at this position
+    BadIndex,
+    /// Already `MAX_ENTRIES` entries
+    Full,
+    /// Not `HH:MM` or `HH:MM:SS`
+    BadTime,
+}
+
+/// Effect of an entry, with its parameters
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EffectKind {
+    Plasma,
+    Fire,
+    Starfield,
+    Rain,
+    Sparkle,
+    Breathing(Color),
+    ColorWash,
+    Life(Rule),
+}
+
+impl FromStr for EffectKind {
+    type Err = Error;
+
+    /// Parse a name followed by the parameter of the effect, if any:
+    /// `plasma`, `fire`, `starfield`, `rain`, `sparkle`, `breathing <colour>`,
+    /// `wash` or `life [<rule>]`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, argument) = match s.trim().split_once(' ') {
+            Some((name, argument)) => (name, Some(argument.trim())),
+            None => (s.trim(), None),
+        };
+        let kind = match (name, argument) {
+            ("plasma", None) => EffectKind::Plasma,
+            ("fire", None) => EffectKind::Fire,
+            ("starfield", None) => EffectKind::Starfield,
+            ("rain", None) => EffectKind::Rain,
+            ("sparkle", None) => EffectKind::Sparkle,
+            ("breathing", Some(color)) => {
+                EffectKind::Breathing(color.parse().map_err(|_| Error::BadArguments)?)
+            }
+            ("wash", None) => EffectKind::ColorWash,
+            ("life", None) => EffectKind::Life(Rule::LIFE),
+            ("life", Some(rule)) => {
+                EffectKind::Life(rule.parse().map_err(|_| Error::BadArguments)?)
+            }
+            ("breathing", None) => return Err(Error::BadArguments),
+            (_, None) => return Err(Error::UnknownEffect),
+            (_, Some(_)) => return Err(Error::BadArguments),
+        };
+        return Ok(kind);
+    }
+}
+
+impl fmt::Display for EffectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            EffectKind::Plasma => write!(f, "plasma"),
+            EffectKind::Fire => write!(f, "fire"),
+            EffectKind::Starfield => write!(f, "starfield"),
+            EffectKind::Rain => write!(f, "rain"),
+            EffectKind::Sparkle => write!(f, "sparkle"),
+            EffectKind::Breathing(color) => write!(f, "breathing {color}"),
+            EffectKind::ColorWash => write!(f, "wash"),
+            EffectKind::Life(rule) => write!(f, "life {rule}"),
+        };
+    }
+}
+
+/// What an entry shows
+#[derive(Clone, Debug, PartialEq)]
+pub enum Content {
+    /// Markup scrolling across the panel
+    Text(String<MAX_TEXT_LEN>),
+    Effect(EffectKind),
+    /// Frames at this index of the animations given to the `Scheduler`
+    Animation(u8),
+    /// Time of day scrolling across the panel, once set with `time`
+    Clock,
+}
+
+impl FromStr for Content {
+    type Err = Error;
+
+    /// Parse `text <markup>`, `effect <effect>`, `animation <index>` or
+    /// `clock`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (keyword, rest) = s.split_once(' ').unwrap_or((s, ""));
+        let content = match keyword {
+            "text" => {
+                Markup::new(rest).map_err(|_| Error::BadText)?;
+                Content::Text(String::from_str(rest).map_err(|_| Error::BadText)?)
+            }
+            "effect" => Content::Effect(rest.parse()?),
+            "animation" => {
+                Content::Animation(rest.trim().parse().map_err(|_| Error::BadArguments)?)
+            }
+            "clock" if rest.trim().is_empty() => Content::Clock,
+            "clock" => return Err(Error::BadArguments),
+            _ => return Err(Error::UnknownContent),
+        };
+        return Ok(content);
+    }
+}
+
+impl fmt::Display for Content {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Content::Text(text) => write!(f, "text {text}"),
+            Content::Effect(kind) => write!(f, "effect {kind}"),
+            Content::Animation(index) => write!(f, "animation {index}"),
+            Content::Clock => write!(f, "clock"),
+        };
+    }
+}
+
+/// How long an entry is shown
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Length {
+    /// In milliseconds
+    Duration(u32),
+    /// Passes of the text, loops of the animation, or times `CYCLE_MS` for
+    /// an effect
+    Times(u32),
+}
+
+impl FromStr for Length {
+    type Err = Error;
+
+    /// Parse `<n>s`, `<n>ms` or `x<n>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |n: &str| n.parse::<u32>().map_err(|_| Error::BadLength);
+        if let Some(count) = s.strip_prefix('x') {
+            return Ok(Length::Times(number(count)?));
+        }
+        if let Some(ms) = s.strip_suffix("ms") {
+            return Ok(Length::Duration(number(ms)?));
+        }
+        if let Some(seconds) = s.strip_suffix('s') {
+            let ms = number(seconds)?.checked_mul(1000).ok_or(Error::BadLength)?;
+            return Ok(Length::Duration(ms));
+        }
+        return Err(Error::BadLength);
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match *self {
+            Length::Duration(ms) if ms % 1000 == 0 => write!(f, "{}s", ms / 1000),
+            Length::Duration(ms) => write!(f, "{ms}ms"),
+            Length::Times(count) => write!(f, "x{count}"),
+        };
+    }
+}
+
+/// Something to show, for how long, and how it replaces the previous entry
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub content: Content,
+    pub length: Length,
+    pub transition: Transition,
+}
+
+impl FromStr for Entry {
+    type Err = Error;
+
+    /// Parse `<length> [<transition>] <content>`, such as
+    /// `10s crossfade:500 text {red}Hello`. Without a transition the entry
+    /// replaces the previous one at once.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (length, rest) = s.trim().split_once(' ').ok_or(Error::BadArguments)?;
+        let (word, after) = rest.split_once(' ').unwrap_or((rest, ""));
+        let (transition, content) = match word.parse() {
+            Ok(transition) => (transition, after),
+            Err(_) => (Transition::CUT, rest),
+        };
+        return Ok(Entry {
+            content: content.parse()?,
+            length: length.parse()?,
+            transition,
+        });
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{} {} {}", self.length, self.transition, self.content);
+    }
+}
+
+/// Order in which the entries are played
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Order {
+    Sequential,
+    /// In a new random order every time the playlist has been gone through
+    Shuffle,
+}
+
+/// Change to a playlist, to its scheduler or to the panel profile, sent as a
+/// line of text on the serial line:
+///
+/// - `clear`: remove every entry
+/// - `add <entry>`: add an entry at the end, see `Entry::from_str`
+/// - `insert <index> <entry>`: add an entry before the one at `index`
+/// - `remove <index>`: remove the entry at `index`, counting from 0
+/// - `order sequential` or `order shuffle`
+/// - `time HH:MM[:SS]`: set the time of day shown by the clock
+/// - `profile <setting>`: change the panel profile, see `Setting::from_str`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Clear,
+    Add(Entry),
+    Insert(usize, Entry),
+    Remove(usize),
+    Order(Order),
+    /// Seconds since midnight
+    Time(u32),
+    /// Change to the orientation or colour correction of the panel, which
+    /// the playlist leaves to the caller
+    Profile(Setting),
+}
+
+impl FromStr for Command {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (keyword, rest) = s.split_once(' ').unwrap_or((s, ""));
+        let index = |s: &str| s.parse::<usize>().map_err(|_| Error::BadIndex);
+        let command = match keyword {
+            "clear" if rest.is_empty() => Command::Clear,
+            "add" => Command::Add(rest.parse()?),
+            "insert" => {
+                let (at, entry) = rest.split_once(' ').ok_or(Error::BadArguments)?;
+                Command::Insert(index(at)?, entry.parse()?)
+            }
+            "remove" => Command::Remove(index(rest.trim())?),
+            "order" => match rest.trim() {
+                "sequential" => Command::Order(Order::Sequential),
+                "shuffle" => Command::Order(Order::Shuffle),
+                _ => return Err(Error::BadArguments),
+            },
+            "time" => Command::Time(parse_time(rest.trim()).ok_or(Error::BadTime)?),
+            "profile" => Command::Profile(rest.parse().map_err(|_| Error::BadArguments)?),
+            "clear" => return Err(Error::BadArguments),
+            _ => return Err(Error::UnknownCommand),
+        };
+        return Ok(command);
+    }
+}
+
+/// Seconds since midnight of `HH:MM` or `HH:MM:SS`
+fn parse_time(s: &str) -> Option<u32> {
+    let mut fields = s.split(':').map(|field| field.parse::<u32>().ok());
+    let hours = fields.next()??;
+    let minutes = fields.next()??;
+    let seconds = fields.next().unwrap_or(Some(0))?;
+    if fields.next().is_some() || hours > 23 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+    return Some(hours * 3600 + minutes * 60 + seconds);
+}
+
+/// Entries shown one after the other when no image is received
+#[derive(Clone, Debug, PartialEq)]
+pub struct Playlist {
+    entries: Vec<Entry, MAX_ENTRIES>,
+    pub order: Order,
+}
+
+impl Playlist {
+    pub const fn new() -> Self {
+        return Playlist {
+            entries: Vec::new(),
+            order: Order::Sequential,
+        };
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        return &self.entries;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.entries.is_empty();
+    }
+
+    /// Carry out `command`, `time` being left to the scheduler and `profile`
+    /// to the caller
+    pub fn apply(&mut self, command: &Command) -> Result<(), Error> {
+        match command {
+            Command::Clear => self.entries.clear(),
+            Command::Add(entry) => self.entries.push(entry.clone()).map_err(|_| Error::Full)?,
+            Command::Insert(index, entry) => {
+                if *index > self.entries.len() {
+                    return Err(Error::BadIndex);
+                }
+                self.entries
+                    .insert(*index, entry.clone())
+                    .map_err(|_| Error::Full)?;
+            }
+            Command::Remove(index) => {
+                if *index >= self.entries.len() {
+                    return Err(Error::BadIndex);
+                }
+                self.entries.remove(*index);
+            }
+            Command::Order(order) => self.order = *order,
+            Command::Time(_) | Command::Profile(_) => {}
+        }
+        return Ok(());
+    }
+}
+
+impl Default for Playlist {
+    fn default() -> Self {
+        return Playlist::new();
+    }
+}
+
+/// Parse the commands building a playlist from scratch, one per line, as
+/// written by `Display`
+impl FromStr for Playlist {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut playlist = Playlist::new();
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            playlist.apply(&line.parse()?)?;
+        }
+        return Ok(playlist);
+    }
+}
+
+/// Written as the commands building it from scratch, one per line, so that
+/// it can be stored as text and sent again
+impl fmt::Display for Playlist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.order == Order::Shuffle {
+            writeln!(f, "order shuffle")?;
+        }
+        for entry in &self.entries {
+            writeln!(f, "add {entry}")?;
+        }
+        return Ok(());
+    }
+}
+
+/// State of the effect being shown
+enum Running {
+    Plasma(Plasma<'static>),
+    Fire(Fire),
+    Starfield(Starfield),
+    Rain(Rain),
+    Sparkle(Sparkle),
+    Breathing(Breathing),
+    ColorWash(ColorWash),
+    Life(Automaton),
+}
+
+impl Running {
+    fn new(kind: EffectKind, seed: u32) -> Self {
+        return match kind {
+            EffectKind::Plasma => Running::Plasma(Plasma::new()),
+            EffectKind::Fire => Running::Fire(Fire::new(seed)),
+            EffectKind::Starfield => Running::Starfield(Starfield::new(seed)),
+            EffectKind::Rain => Running::Rain(Rain::new(seed)),
+            EffectKind::Sparkle => Running::Sparkle(Sparkle::new(seed)),
+            EffectKind::Breathing(color) => Running::Breathing(Breathing::new(color)),
+            EffectKind::ColorWash => Running::ColorWash(ColorWash::new()),
+            EffectKind::Life(rule) => Running::Life(Automaton::new(rule, seed)),
+        };
+    }
+
+    fn render(&mut self, t: Instant, image: &mut Image) {
+        match self {
+            Running::Plasma(effect) => effect.render(t, image),
+            Running::Fire(effect) => effect.render(t, image),
+            Running::Starfield(effect) => effect.render(t, image),
+            Running::Rain(effect) => effect.render(t, image),
+            Running::Sparkle(effect) => effect.render(t, image),
+            Running::Breathing(effect) => effect.render(t, image),
+            Running::ColorWash(effect) => effect.render(t, image),
+            Running::Life(effect) => Effect::render(effect, t, image),
+        }
+    }
+}
+
+/// Plays a playlist: keeps track of the entry being shown, for how long, and
+/// of the state of its effect
+pub struct Scheduler<'a> {
+    playlist: Playlist,
+    animations: &'a [&'a [Frame]],
+    /// Indices of the entries in the order of this pass
+    order: [u8; MAX_ENTRIES],
+    position: usize,
+    /// When the current entry started, `None` until its first frame
+    started: Option<Instant>,
+    running: Option<Running>,
+    rng: Rng,
+    /// Time of day when the monotonic timer was at 0, once set
+    clock_ms: Option<u32>,
+}
+
+impl<'a> Scheduler<'a> {
+    /// Play `playlist`, whose animation entries refer to `animations`,
+    /// shuffling it with `seed`
+    pub fn new(playlist: Playlist, animations: &'a [&'a [Frame]], seed: u32) -> Self {
+        let mut scheduler = Scheduler {
+            playlist,
+            animations,
+            order: [0; MAX_ENTRIES],
+            position: 0,
+            started: None,
+            running: None,
+            rng: Rng::new(seed),
+            clock_ms: None,
+        };
+        scheduler.restart();
+        return scheduler;
+    }
+
+    pub fn playlist(&self) -> &Playlist {
+        return &self.playlist;
+    }
+
+    /// Carry out `command`, received at `t`, and tell whether the playlist
+    /// changed, in which case it starts again from the beginning
+    pub fn apply(&mut self, command: &Command, t: Instant) -> Result<bool, Error> {
+        match *command {
+            Command::Time(seconds) => {
+                let day_ms = time_of_day(t);
+                self.clock_ms = Some((seconds * 1000 + DAY_MS - day_ms) % DAY_MS);
+                return Ok(false);
+            }
+            Command::Profile(_) => return Ok(false),
+            _ => {}
+        }
+        self.playlist.apply(command)?;
+        self.restart();
+        return Ok(true);
+    }
+
+    /// Start again from the first entry, in a new order if shuffling
+    pub fn restart(&mut self) {
+        for (i, index) in self.order.iter_mut().enumerate() {
+            *index = i as u8;
+        }
+        if self.playlist.order == Order::Shuffle {
+            // Fisher-Yates
+            for i in (1..self.playlist.entries.len()).rev() {
+                let j = self.rng.below(i as u32 + 1) as usize;
+                self.order.swap(i, j);
+            }
+        }
+        self.position = 0;
+        self.started = None;
+        self.running = None;
+    }
+
+    fn next_entry(&mut self) {
+        self.position += 1;
+        self.started = None;
+        self.running = None;
+        if self.position == self.playlist.entries.len() {
+            self.restart();
+        }
+    }
+
+    /// Draw into `image` what is shown at `t`, moving on to the next entries once the current one
+    /// is over. Returns `None` without drawing anything if the playlist is
+    /// empty, and otherwise how to replace the image shown so far: the
+    /// transition of the entry on its first frame, and a cut afterwards.
+    pub fn render(&mut self, t: Instant, image: &mut Image) -> Option<Transition> {
+        let len = self.playlist.entries.len();
+        if len == 0 {
+            return None;
+        }
+        // Entries over at once, such as missing animations, are skipped,
+        // but only once each
+        for _ in 0..len {
+            let index = self.order[self.position] as usize;
+            let first = self.started.is_none();
+            if first {
+                self.started = Some(t);
+                if let Content::Effect(kind) = self.playlist.entries[index].content {
+                    self.running = Some(Running::new(kind, self.rng.next()));
+                }
+            }
+            let elapsed = millis_between(self.started.unwrap_or(t), t);
+            let elapsed = u32::try_from(elapsed).unwrap_or(u32::MAX);
+            if self.draw(index, t, elapsed, image) {
+                let entry = &self.playlist.entries[index];
+                return Some(if first {
+                    entry.transition
+                } else {
+                    Transition::CUT
+                });
+            }
+            self.next_entry();
+        }
+        *image = Image::default();
+        return Some(Transition::CUT);
+    }
+
+    /// Draw entry `index`, started `elapsed` ms ago, unless it is over
+    fn draw(&mut self, index: usize, t: Instant, elapsed: u32, image: &mut Image) -> bool {
+        let entry = &self.playlist.entries[index];
+        let over = |period: u32| match entry.length {
+            Length::Duration(ms) => elapsed >= ms,
+            Length::Times(count) => elapsed >= period.saturating_mul(count),
+        };
+        *image = Image::default();
+        match &entry.content {
+            Content::Text(text) => return scroll(text, elapsed, over, image),
+            Content::Clock => {
+                let text = self.clock_text(t);
+                return scroll(&text, elapsed, over, image);
+            }
+            Content::Animation(animation) => {
+                let frames = self.animations.get(*animation as usize).copied();
+                let frames = frames.unwrap_or_default();
+                let total: u32 = frames.iter().map(|frame| frame.delay_ms).sum();
+                if total == 0 || over(total) {
+                    return false;
+                }
+                let mut at = elapsed % total;
+                for frame in frames {
+                    if at < frame.delay_ms {
+                        *image = frame.image.clone();
+                        break;
+                    }
+                    at -= frame.delay_ms;
+                }
+                return true;
+            }
+            Content::Effect(_) => {
+                if over(CYCLE_MS) {
+                    return false;
+                }
+                if let Some(running) = &mut self.running {
+                    running.render(t, image);
+                }
+                return true;
+            }
+        }
+    }
+
+    /// Time of day as `HH:MM`, or dashes if it has not been set
+    fn clock_text(&self, t: Instant) -> String<8> {
+        let mut text = String::new();
+        let _ = match self.clock_ms {
+            Some(clock_ms) => {
+                let minutes = (clock_ms + time_of_day(t)) % DAY_MS / 60_000;
+                write!(text, "{:02}:{:02}", minutes / 60, minutes % 60)
+            }
+            None => write!(text, "--:--"),
+        };
+        return text;
+    }
+}
+
+/// Draw `text` as it is `elapsed` ms after it started scrolling, unless
+/// `over` says it is over given the duration of a pass
+fn scroll(text: &str, elapsed: u32, over: impl Fn(u32) -> bool, image: &mut Image) -> bool {
+    let mut scroller = TextScroller::new(text, Typeface::Proportional(&FONT_6X8));
+    scroller.markup = true;
+    if over(scroller.pass_len::<8, 8>() * STEP_MS) {
+        return false;
+    }
+    scroller.draw(elapsed / STEP_MS, image);
+    return true;
+}
@@ -0,0 +1,317 @@
+use crate::{Color, Image};
+
+/// Netpbm flavours understood by the encoder
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Plain PPM (`P3`), samples written as decimal text
+    Ascii,
+    /// Raw PPM (`P6`), samples written as bytes
+    Binary,
+    /// PAM (`P7`) with the `RGB` tuple type
+    Pam,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The data stops before the end of the header or of the raster
+    UnexpectedEnd,
+    /// The data does not start with `P3`, `P6` or `P7`
+    BadMagic,
+    /// A header field is missing, repeated or not a number
+    BadHeader,
+    /// The image is not 8x8
+    BadDimensions { width: u32, height: u32 },
+    /// The maximum sample value is 0 or needs more than 8 bits
+    BadMaxval(u32),
+    /// The PAM depth is neither 3 (RGB) nor 4 (RGB_ALPHA)
+    BadDepth(u32),
+    /// A plain PPM sample is not a number or exceeds the maximum value
+    BadSample,
+    /// The output buffer is too small for the encoded image
+    BufferTooSmall,
+}
+
+/// Size of an image encoded by `encode()` with `Format::Binary`
+pub const BINARY_PPM_LEN: usize = 11 + 192;
+/// Size of an image encoded by `encode()` with `Format::Pam`
+pub const PAM_LEN: usize = 59 + 192;
+/// Upper bound for the size of an image encoded by `encode()` with `Format::Ascii`
+pub const MAX_ASCII_PPM_LEN: usize = 11 + 8 * (8 * 11 + 7 * 2 + 1);
+
+/// Decode the first image in `data`, and return it together with the number
+/// of bytes consumed, so that the next image of a stream starts right after.
+pub fn decode(data: &[u8]) -> Result<(Image, usize), Error> {
+    let mut reader = Reader { data, pos: 0 };
+    let image = match reader.take(2)? {
+        b"P3" => reader.ppm(true)?,
+        b"P6" => reader.ppm(false)?,
+        b"P7" => reader.pam()?,
+        _ => return Err(Error::BadMagic),
+    };
+    return Ok((image, reader.pos));
+}
+
+/// Encode `image` into `out` and return the number of bytes written.
+pub fn encode(image: &Image, format: Format, out: &mut [u8]) -> Result<usize, Error> {
+    let mut writer = Writer { out, pos: 0 };
+    match format {
+        Format::Ascii => {
+            writer.push(b"P3\n8 8\n255\n")?;
+            for row in 0..8 {
+                for (col, pixel) in image.row(row).iter().enumerate() {
+                    if col != 0 {
+                        writer.push(b"  ")?;
+                    }
+                    writer.number(pixel.r)?;
+                    writer.push(b" ")?;
+                    writer.number(pixel.g)?;
+                    writer.push(b" ")?;
+                    writer.number(pixel.b)?;
+                }
+                writer.push(b"\n")?;
+            }
+        }
+        Format::Binary => {
+            writer.push(b"P6\n8 8\n255\n")?;
+            writer.push(image.as_ref())?;
+        }
+        Format::Pam => {
+            writer.push(b"P7\nWIDTH 8\nHEIGHT 8\nDEPTH 3\nMAXVAL 255\nTUPLTYPE RGB\nENDHDR\n")?;
+            writer.push(image.as_ref())?;
+        }
+    }
+    return Ok(writer.pos);
+}
+
+/// Iterator over a stream of concatenated PPM or PAM images, as produced by
+/// `ppmtoppm`, `pamcat` or repeated calls to `encode()`.
+pub struct Frames<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Frames<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        return Frames { data };
+    }
+}
+
+impl Iterator for Frames<'_> {
+    type Item = Result<Image, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Trailing whitespace after the last image is not an error
+        let start = self.data.iter().position(|b| !b.is_ascii_whitespace())?;
+        match decode(&self.data[start..]) {
+            Ok((image, len)) => {
+                self.data = &self.data[start + len..];
+                return Some(Ok(image));
+            }
+            Err(error) => {
+                // Do not try to resynchronise inside a broken stream
+                self.data = &[];
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() - self.pos < len {
+            return Err(Error::UnexpectedEnd);
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        return Ok(bytes);
+    }
+
+    /// Skip whitespace and `#` comments, which run up to the end of the line
+    fn skip_blanks(&mut self) {
+        while let Some(&b) = self.data.get(self.pos) {
+            if b == b'#' {
+                while self.data.get(self.pos).is_some_and(|&b| b != b'\n') {
+                    self.pos += 1;
+                }
+            } else if b.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn token(&mut self) -> Result<&'a [u8], Error> {
+        self.skip_blanks();
+        let start = self.pos;
+        while self
+            .data
+            .get(self.pos)
+            .is_some_and(|b| !b.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(Error::UnexpectedEnd);
+        }
+        return Ok(&self.data[start..self.pos]);
+    }
+
+    fn number(&mut self, error: Error) -> Result<u32, Error> {
+        let token = self.token()?;
+        let mut value: u32 = 0;
+        for &digit in token {
+            if !digit.is_ascii_digit() {
+                return Err(error);
+            }
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((digit - b'0') as u32))
+                .ok_or(error)?;
+        }
+        return Ok(value);
+    }
+
+    /// Consume the single whitespace character separating a binary header
+    /// from the raster
+    fn end_of_header(&mut self) -> Result<(), Error> {
+        match self.take(1)? {
+            [b] if b.is_ascii_whitespace() => return Ok(()),
+            _ => return Err(Error::BadHeader),
+        }
+    }
+
+    fn ppm(&mut self, ascii: bool) -> Result<Image, Error> {
+        let width = self.number(Error::BadHeader)?;
+        let height = self.number(Error::BadHeader)?;
+        let maxval = self.number(Error::BadHeader)?;
+        check_header(width, height, maxval)?;
+
+        let mut image = Image::default();
+        if ascii {
+            for byte in image.as_mut().iter_mut() {
+                let sample = self.number(Error::BadSample)?;
+                if sample > maxval {
+                    return Err(Error::BadSample);
+                }
+                *byte = rescale(sample, maxval);
+            }
+        } else {
+            self.end_of_header()?;
+            let raster = self.take(192)?;
+            for (byte, &sample) in image.as_mut().iter_mut().zip(raster) {
+                *byte = rescale(sample as u32, maxval);
+            }
+        }
+        return Ok(image);
+    }
+
+    fn pam(&mut self) -> Result<Image, Error> {
+        let mut width = None;
+        let mut height = None;
+        let mut depth = None;
+        let mut maxval = None;
+        loop {
+            let field = match self.token()? {
+                b"ENDHDR" => break,
+                b"WIDTH" => &mut width,
+                b"HEIGHT" => &mut height,
+                b"DEPTH" => &mut depth,
+                b"MAXVAL" => &mut maxval,
+                b"TUPLTYPE" => {
+                    // DEPTH already tells how to read the tuples, so only
+                    // the line is skipped here
+                    while self.data.get(self.pos).is_some_and(|&b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                    continue;
+                }
+                _ => return Err(Error::BadHeader),
+            };
+            if field.is_some() {
+                return Err(Error::BadHeader);
+            }
+            *field = Some(self.number(Error::BadHeader)?);
+        }
+        let width = width.ok_or(Error::BadHeader)?;
+        let height = height.ok_or(Error::BadHeader)?;
+        let depth = depth.ok_or(Error::BadHeader)?;
+        let maxval = maxval.ok_or(Error::BadHeader)?;
+        check_header(width, height, maxval)?;
+        if depth != 3 && depth != 4 {
+            return Err(Error::BadDepth(depth));
+        }
+        self.end_of_header()?;
+
+        let raster = self.take(64 * depth as usize)?;
+        let mut image = Image::default();
+        for (i, tuple) in raster.chunks_exact(depth as usize).enumerate() {
+            let mut color = Color {
+                r: rescale(tuple[0] as u32, maxval),
+                g: rescale(tuple[1] as u32, maxval),
+                b: rescale(tuple[2] as u32, maxval),
+            };
+            if depth == 4 {
+                // RGB_ALPHA: the panel has no transparency, so composite over black
+                let alpha = rescale(tuple[3] as u32, maxval) as u32;
+                color.r = (color.r as u32 * alpha / 255) as u8;
+                color.g = (color.g as u32 * alpha / 255) as u8;
+                color.b = (color.b as u32 * alpha / 255) as u8;
+            }
+            image[(i / 8, i % 8)] = color;
+        }
+        return Ok(image);
+    }
+}
+
+fn check_header(width: u32, height: u32, maxval: u32) -> Result<(), Error> {
+    if width != 8 || height != 8 {
+        return Err(Error::BadDimensions { width, height });
+    }
+    if maxval == 0 || maxval > 255 {
+        return Err(Error::BadMaxval(maxval));
+    }
+    return Ok(());
+}
+
+/// Bring a sample in `0..=maxval` to `0..=255`, rounding to the nearest value
+fn rescale(sample: u32, maxval: u32) -> u8 {
+    return ((sample.min(maxval) * 255 + maxval / 2) / maxval) as u8;
+}
+
+struct Writer<'a> {
+    out: &'a mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn push(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let end = self.pos + bytes.len();
+        if end > self.out.len() {
+            return Err(Error::BufferTooSmall);
+        }
+        self.out[self.pos..end].copy_from_slice(bytes);
+        self.pos = end;
+        return Ok(());
+    }
+
+    fn number(&mut self, value: u8) -> Result<(), Error> {
+        let digits = [
+            b'0' + value / 100,
+            b'0' + value / 10 % 10,
+            b'0' + value % 10,
+        ];
+        let skip = if value >= 100 {
+            0
+        } else if value >= 10 {
+            1
+        } else {
+            2
+        };
+        return self.push(&digits[skip..]);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::transition::Transition;
+use crate::{Image, PanelImage};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use heapless::Vec as Buffer;
+
+/// Byte announcing the start of a new image on the serial line. It can never
+/// appear inside an image, whose bytes are capped to 254.
+pub const START: u8 = 0xff;
+
+/// Size of a `width` x `height` image on the serial line, start byte included
+pub const fn frame_len(width: usize, height: usize) -> usize {
+    return 1 + 3 * width * height;
+}
+
+/// Size of a panel image on the serial line, start byte included
+pub const FRAME_LEN: usize = frame_len(8, 8);
+
+/// Byte following two start bytes to announce a command instead of a
+/// transition header. Neither this byte nor the start byte can appear in
+/// UTF-8 text.
+pub const COMMAND: u8 = 0xfe;
+
+/// Longest command, its final newline excluded
+pub const MAX_COMMAND_LEN: usize = 128;
+
+/// Encode `image` as it is sent on the serial line: the start byte followed by
+/// the RGB bytes of every pixel, row by row. `frame` must hold at least
+/// `frame_len(W, H)` bytes, and the number of bytes written is returned.
+pub fn write_frame<const W: usize, const H: usize>(image: &Image<W, H>, frame: &mut [u8]) -> usize {
+    let len = frame_len(W, H);
+    frame[0] = START;
+    for (byte, &value) in frame[1..len].iter_mut().zip(image.as_bytes()) {
+        *byte = value.min(START - 1);
+    }
+    return len;
+}
+
+/// Encode a panel image as it is sent on the serial line
+pub fn encode_frame(image: &PanelImage) -> [u8; FRAME_LEN] {
+    let mut frame = [START; FRAME_LEN];
+    write_frame(image, &mut frame);
+    return frame;
+}
+
+/// Size of a `width` x `height` image announced with a transition on the
+/// serial line: two start bytes, the transition header, then the image bytes
+pub const fn transition_frame_len(width: usize, height: usize) -> usize {
+    return 4 + 3 * width * height;
+}
+
+/// Encode `image` preceded by `transition`, which the board uses to replace
+/// the image shown by this one. `frame` must hold at least
+/// `transition_frame_len(W, H)` bytes, and the number of bytes written is
+/// returned.
+pub fn write_transition_frame<const W: usize, const H: usize>(
+    image: &Image<W, H>,
+    transition: &Transition,
+    frame: &mut [u8],
+) -> usize {
+    let len = 3 + write_frame(image, &mut frame[3..]);
+    let header = transition.to_header();
+    frame[..4].copy_from_slice(&[START, START, header[0], header[1]]);
+    return len;
+}
+
+/// Encode `command`, a line of text without its newline, as it is sent on
+/// the serial line: two start bytes, the command byte, the text and a
+/// newline. `frame` must hold at least `command.len() + 4` bytes, and the
+/// number of bytes written is returned.
+pub fn write_command(command: &str, frame: &mut [u8]) -> usize {
+    let len = command.len() + 4;
+    frame[..3].copy_from_slice(&[START, START, COMMAND]);
+    frame[3..len - 1].copy_from_slice(command.as_bytes());
+    frame[len - 1] = b'\n';
+    return len;
+}
+
+/// What happened after a byte was given to a `Receiver`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The last byte of an image has been stored
+    Complete,
+    /// A start byte arrived while an image was incomplete, whose `received`
+    /// bytes will be overwritten
+    Restarted { received: usize },
+    /// A byte arrived after a complete image and before the next start byte,
+    /// and was dropped
+    Ignored,
+    /// The newline ending a command has been received, the command being
+    /// available from `Receiver::command()`
+    Command,
+}
+
+/// Serial line decoder, storing the incoming bytes in an image until it is
+/// complete, then ignoring everything until the next start byte. Two start
+/// bytes in a row announce a two-byte transition header before the image,
+/// or a line of text if they are followed by the command byte.
+pub struct Receiver {
+    next_pos: usize,
+    /// Whether the last byte was a start byte
+    after_start: bool,
+    /// Position in the header while receiving it
+    header_pos: Option<usize>,
+    header: [u8; 2],
+    /// Transition of the image being received
+    announced: Option<Transition>,
+    /// Transition of the last complete image
+    transition: Option<Transition>,
+    /// Whether the bytes go to `command` instead of the image
+    in_command: bool,
+    command: Buffer<u8, MAX_COMMAND_LEN>,
+}
+
+impl Receiver {
+    /// Position used while waiting for a start byte
+    const WAITING: usize = usize::MAX;
+
+    pub const fn new() -> Self {
+        // Like after a start byte, so that a board reset in the middle of a
+        // stream does not wait for the next image
+        return Receiver {
+            next_pos: 0,
+            after_start: false,
+            header_pos: None,
+            header: [0; 2],
+            announced: None,
+            transition: None,
+            in_command: false,
+            command: Buffer::new(),
+        };
+    }
+
+    /// Handle a byte received on the serial line, storing it into `image`.
+    /// The sender must use the same image size.
+    pub fn receive<const W: usize, const H: usize>(
+        &mut self,
+        byte: u8,
+        image: &mut Image<W, H>,
+    ) -> Option<Event> {
+        if byte == START {
+            let received = self.next_pos;
+            self.next_pos = 0;
+            self.header_pos = self.after_start.then_some(0);
+            self.after_start = true;
+            self.announced = None;
+            self.in_command = false;
+            if received != 0 && received != Self::WAITING {
+                return Some(Event::Restarted { received });
+            }
+            return None;
+        }
+        self.after_start = false;
+        if self.next_pos == Self::WAITING {
+            return Some(Event::Ignored);
+        }
+        if self.in_command {
+            if byte == b'\n' {
+                self.in_command = false;
+                self.next_pos = Self::WAITING;
+                return Some(Event::Command);
+            }
+            if self.command.push(byte).is_err() {
+                // Too long, dropped with everything up to the next start byte
+                self.in_command = false;
+                self.next_pos = Self::WAITING;
+                return Some(Event::Ignored);
+            }
+            return None;
+        }
+        if self.header_pos == Some(0) && byte == COMMAND {
+            self.header_pos = None;
+            self.in_command = true;
+            self.command.clear();
+            return None;
+        }
+        if let Some(pos) = self.header_pos {
+            self.header[pos] = byte;
+            if pos + 1 == self.header.len() {
+                self.header_pos = None;
+                self.announced = Transition::from_header(self.header);
+            } else {
+                self.header_pos = Some(pos + 1);
+            }
+            return None;
+        }
+        let bytes = image.as_bytes_mut();
+        bytes[self.next_pos] = byte;
+        self.next_pos += 1;
+        if self.next_pos == bytes.len() {
+            self.next_pos = Self::WAITING;
+            self.transition = self.announced.take();
+            return Some(Event::Complete);
+        }
+        return None;
+    }
+
+    /// Transition announced before the last complete image, if any
+    pub fn transition(&self) -> Option<Transition> {
+        return self.transition;
+    }
+
+    /// Text of the last complete command, or `None` if it is not valid
+    /// UTF-8. It is kept until the next command is announced.
+    pub fn command(&self) -> Option<&str> {
+        return core::str::from_utf8(&self.command).ok();
+    }
+}
+
+impl Default for Receiver {
+    fn default() -> Self {
+        return Receiver::new();
+    }
+}
+
+/// An image and how long it stays on the matrix
+pub struct Frame<const W: usize = 8, const H: usize = 8> {
+    pub image: Image<W, H>,
+    pub delay_ms: u32,
+}
+
+/// Concatenate the serial encoding of `frames`, as stored in the `.bin` files
+/// which can be sent as-is to the board. Timings are not part of the format and
+/// are left to the sender.
+#[cfg(feature = "alloc")]
+pub fn to_bin<'a, const W: usize, const H: usize>(
+    frames: impl IntoIterator<Item = &'a Frame<W, H>>,
+) -> Vec<u8> {
+    let mut bin = Vec::new();
+    for frame in frames {
+        let start = bin.len();
+        bin.resize(start + frame_len(W, H), 0);
+        write_frame(&frame.image, &mut bin[start..]);
+    }
+    return bin;
+}
@@ -0,0 +1,289 @@
+use crate::{gamma::GAMMA_TAB, Color, Image};
+use alloc::vec::Vec;
+use micromath::F32Ext;
+
+/// RGB picture of any size, 3 bytes per pixel in row-major order
+#[derive(Clone)]
+pub struct RgbImage {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u8>,
+}
+
+impl RgbImage {
+    pub fn new(width: usize, height: usize, color: Color) -> Self {
+        let mut data = Vec::with_capacity(width * height * 3);
+        for _ in 0..width * height {
+            data.extend_from_slice(&[color.r, color.g, color.b]);
+        }
+        return RgbImage {
+            width,
+            height,
+            data,
+        };
+    }
+
+    /// Wrap an existing buffer, which must hold exactly `width * height` pixels
+    pub fn from_raw(width: usize, height: usize, data: Vec<u8>) -> Option<Self> {
+        if width == 0 || height == 0 || data.len() != width * height * 3 {
+            return None;
+        }
+        return Some(RgbImage {
+            width,
+            height,
+            data,
+        });
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Color {
+        let i = 3 * (y * self.width + x);
+        return Color {
+            r: self.data[i],
+            g: self.data[i + 1],
+            b: self.data[i + 2],
+        };
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        let i = 3 * (y * self.width + x);
+        self.data[i..i + 3].copy_from_slice(&[color.r, color.g, color.b]);
+    }
+}
+
+/// How source pixels are combined into a matrix pixel
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Filter {
+    /// Take the source pixel under the centre of the matrix pixel
+    Nearest,
+    /// Interpolate the four source pixels around the centre of the matrix pixel
+    Bilinear,
+    /// Average every source pixel covered by the matrix pixel, weighted by
+    /// the covered area. Best for large reductions.
+    Area,
+}
+
+/// How the source is laid out on the matrix when the aspect ratios differ
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Fit {
+    /// Use the whole source and distort it to the matrix size
+    Stretch,
+    /// Use the whole source, keep its aspect ratio and fill the borders
+    /// with the background colour
+    Fit,
+    /// Cover the whole matrix, keep the aspect ratio and cut the parts of the
+    /// source which do not fit, keeping the centre
+    Fill,
+    /// Only use the given source rectangle, stretched to the matrix size
+    Crop {
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    },
+}
+
+/// How the result is brought back to the 8 bits per channel of the matrix
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dither {
+    /// Round every channel to the closest value
+    None,
+    /// Diffuse the rounding error to the pixels on the right and below
+    FloydSteinberg,
+    /// Add a 4x4 Bayer threshold pattern before rounding
+    Ordered,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub filter: Filter,
+    pub fit: Fit,
+    pub dither: Dither,
+    /// Colour used outside of the picture with `Fit::Fit`
+    pub background: Color,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        return Options {
+            filter: Filter::Area,
+            fit: Fit::Fit,
+            dither: Dither::None,
+            background: Color::default(),
+        };
+    }
+}
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Resample `src` onto an 8x8 image according to `options`. An empty
+/// source, or one whose data is too short for its size, gives an image
+/// filled with the background colour.
+pub fn to_image(src: &RgbImage, options: &Options) -> Image {
+    if src.width == 0 || src.height == 0 || src.data.len() < src.width * src.height * 3 {
+        return Image::new_solid(options.background);
+    }
+    let (w, h) = (src.width as f32, src.height as f32);
+
+    // Source rectangle and destination rectangle on the matrix
+    let (sx, sy, sw, sh, dx, dy, dw, dh) = match options.fit {
+        Fit::Stretch => (0.0, 0.0, w, h, 0, 0, 8, 8),
+        Fit::Fit => {
+            let scale = (8.0 / w).min(8.0 / h);
+            let dw = ((w * scale).round() as usize).clamp(1, 8);
+            let dh = ((h * scale).round() as usize).clamp(1, 8);
+            (0.0, 0.0, w, h, (8 - dw) / 2, (8 - dh) / 2, dw, dh)
+        }
+        Fit::Fill => {
+            let scale = (8.0 / w).max(8.0 / h);
+            let (sw, sh) = (8.0 / scale, 8.0 / scale);
+            ((w - sw) / 2.0, (h - sh) / 2.0, sw, sh, 0, 0, 8, 8)
+        }
+        Fit::Crop {
+            x,
+            y,
+            width,
+            height,
+        } => {
+            // Keep the rectangle inside the source and at least one pixel wide
+            let x = x.min(src.width - 1);
+            let y = y.min(src.height - 1);
+            let width = width.clamp(1, src.width - x);
+            let height = height.clamp(1, src.height - y);
+            (x as f32, y as f32, width as f32, height as f32, 0, 0, 8, 8)
+        }
+    };
+    let (step_x, step_y) = (sw / dw as f32, sh / dh as f32);
+
+    // Resampled channels, still in the 0.0..=255.0 range
+    let mut samples = [[0.0f32; 3]; 64];
+    for row in 0..dh {
+        for col in 0..dw {
+            let x0 = sx + col as f32 * step_x;
+            let y0 = sy + row as f32 * step_y;
+            samples[8 * (dy + row) + dx + col] = match options.filter {
+                Filter::Nearest => nearest(src, x0 + step_x / 2.0, y0 + step_y / 2.0),
+                Filter::Bilinear => bilinear(src, x0 + step_x / 2.0, y0 + step_y / 2.0),
+                Filter::Area => area(src, x0, y0, x0 + step_x, y0 + step_y),
+            };
+        }
+    }
+
+    let mut image = Image::new_solid(options.background);
+    // Rounding errors still to be spread, expressed in LED intensity
+    let mut errors = [[0.0f32; 3]; 64];
+    for row in dy..dy + dh {
+        for col in dx..dx + dw {
+            let mut channels = [0u8; 3];
+            for c in 0..3 {
+                let value = samples[8 * row + col][c];
+                channels[c] = match options.dither {
+                    Dither::None => value.round() as u8,
+                    Dither::Ordered => {
+                        let threshold = (BAYER_4X4[row % 4][col % 4] as f32 + 0.5) / 16.0 - 0.5;
+                        closest_level(intensity(value) + threshold, value)
+                    }
+                    Dither::FloydSteinberg => {
+                        let target = intensity(value) + errors[8 * row + col][c];
+                        let level = closest_level(target, value);
+                        let error = target - GAMMA_TAB[level as usize] as f32;
+                        for (ex, ey, weight) in
+                            [(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)]
+                        {
+                            let (x, y) = (col as i32 + ex, row + ey);
+                            if x >= dx as i32 && x < (dx + dw) as i32 && y < dy + dh {
+                                errors[8 * y + x as usize][c] += error * weight / 16.0;
+                            }
+                        }
+                        level
+                    }
+                };
+            }
+            image[(row, col)] = Color {
+                r: channels[0],
+                g: channels[1],
+                b: channels[2],
+            };
+        }
+    }
+    return image;
+}
+
+fn channels(color: Color) -> [f32; 3] {
+    return [color.r as f32, color.g as f32, color.b as f32];
+}
+
+fn nearest(src: &RgbImage, x: f32, y: f32) -> [f32; 3] {
+    let x = (x as usize).min(src.width - 1);
+    let y = (y as usize).min(src.height - 1);
+    return channels(src.pixel(x, y));
+}
+
+fn bilinear(src: &RgbImage, x: f32, y: f32) -> [f32; 3] {
+    // Pixel centres are at half coordinates
+    let x = (x - 0.5).clamp(0.0, (src.width - 1) as f32);
+    let y = (y - 0.5).clamp(0.0, (src.height - 1) as f32);
+    let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+    let (x1, y1) = ((x0 + 1).min(src.width - 1), (y0 + 1).min(src.height - 1));
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+    let (p00, p10) = (channels(src.pixel(x0, y0)), channels(src.pixel(x1, y0)));
+    let (p01, p11) = (channels(src.pixel(x0, y1)), channels(src.pixel(x1, y1)));
+    let mut result = [0.0; 3];
+    for c in 0..3 {
+        let top = p00[c] + (p10[c] - p00[c]) * fx;
+        let bottom = p01[c] + (p11[c] - p01[c]) * fx;
+        result[c] = top + (bottom - top) * fy;
+    }
+    return result;
+}
+
+fn area(src: &RgbImage, x0: f32, y0: f32, x1: f32, y1: f32) -> [f32; 3] {
+    let mut sum = [0.0; 3];
+    let mut total = 0.0;
+    let last_x = (x1.ceil() as usize).min(src.width);
+    let last_y = (y1.ceil() as usize).min(src.height);
+    for y in y0.floor() as usize..last_y {
+        // Part of the source row covered by the box
+        let cover_y = (y1.min(y as f32 + 1.0) - y0.max(y as f32)).max(0.0);
+        for x in x0.floor() as usize..last_x {
+            let weight = cover_y * (x1.min(x as f32 + 1.0) - x0.max(x as f32)).max(0.0);
+            let pixel = channels(src.pixel(x, y));
+            for c in 0..3 {
+                sum[c] += pixel[c] * weight;
+            }
+            total += weight;
+        }
+    }
+    if total == 0.0 {
+        return nearest(src, x0, y0);
+    }
+    return [sum[0] / total, sum[1] / total, sum[2] / total];
+}
+
+/// LED intensity produced by a fractional channel value, interpolated
+/// from the gamma table
+fn intensity(value: f32) -> f32 {
+    let value = value.clamp(0.0, 255.0);
+    let low = value.floor() as usize;
+    let high = (low + 1).min(255);
+    let fract = value - low as f32;
+    return GAMMA_TAB[low] as f32 + (GAMMA_TAB[high] as f32 - GAMMA_TAB[low] as f32) * fract;
+}
+
+/// Channel value whose gamma-corrected intensity is the closest to `target`.
+/// Several values give the same intensity in the dark range, in which case
+/// the rounded `value` is kept if it is one of them.
+fn closest_level(target: f32, value: f32) -> u8 {
+    let above = GAMMA_TAB
+        .partition_point(|&level| (level as f32) < target)
+        .min(255);
+    let mut level = GAMMA_TAB[above];
+    if above > 0 && target - (GAMMA_TAB[above - 1] as f32) <= level as f32 - target {
+        level = GAMMA_TAB[above - 1];
+    }
+    let value = value.clamp(0.0, 255.0).round() as u8;
+    if GAMMA_TAB[value as usize] == level {
+        return value;
+    }
+    // Smallest value giving this intensity
+    return GAMMA_TAB.partition_point(|&l| l < level) as u8;
+}
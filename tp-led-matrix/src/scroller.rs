@@ -0,0 +1,212 @@
+use crate::font::Typeface;
+use crate::hsv::HUE_STEPS;
+use crate::markup::Markup;
+use crate::{Color, Image};
+use embedded_graphics::prelude::*;
+
+/// Frames during which blinking text is shown, then hidden
+pub const BLINK_FRAMES: u32 = 8;
+/// Hue difference between two columns of rainbow text
+const RAINBOW_STEP: u32 = 64;
+/// Hue change of rainbow text from one frame to the next
+const RAINBOW_SPEED: u32 = 32;
+
+/// Where the text goes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// What happens once the text has gone through
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Repeat {
+    /// Come back from the other side
+    Loop,
+    /// Stay out of sight
+    Once,
+    /// Go back and forth between the two positions where it touches the
+    /// edges of the image, or stand still if it fits exactly
+    Bounce,
+}
+
+/// Text moving across an image, one pixel every `frames_per_pixel` frames.
+/// The colour changes every time the text has gone through, going over
+/// `colors` in turn. If `markup` is set, `text` is taken as `Markup`, whose
+/// styles override the colour.
+#[derive(Copy, Clone, Debug)]
+pub struct TextScroller<'a> {
+    pub text: &'a str,
+    pub font: Typeface<'a>,
+    pub colors: &'a [Color],
+    pub direction: Direction,
+    pub repeat: Repeat,
+    pub frames_per_pixel: u32,
+    pub markup: bool,
+}
+
+impl<'a> TextScroller<'a> {
+    /// White text looping to the left, one pixel per frame
+    pub const fn new(text: &'a str, font: Typeface<'a>) -> Self {
+        return TextScroller {
+            text,
+            font,
+            colors: &[Color::WHITE],
+            direction: Direction::Left,
+            repeat: Repeat::Loop,
+            frames_per_pixel: 1,
+            markup: false,
+        };
+    }
+
+    /// Width and height of the rendered text
+    pub fn text_size(&self) -> Size {
+        if !self.markup {
+            return self.font.text_size(self.text);
+        }
+        let (mut x, mut width) = (0, 0);
+        for span in Markup::new_unchecked(self.text).spans() {
+            let metrics = self.font.measure(span.text);
+            width = width.max(x + metrics.bounding_box.size.width as i32);
+            x += metrics.next_position.x;
+        }
+        let height = self.font.text_size("").height;
+        return Size::new(width as u32, height);
+    }
+
+    /// First and last positions along the direction of movement in a
+    /// `width` x `height` image, and the position across it
+    fn course(&self, width: i32, height: i32) -> (i32, i32, i32) {
+        let size = self.text_size();
+        let (text_width, text_height) = (size.width as i32, size.height as i32);
+        let horizontal = matches!(self.direction, Direction::Left | Direction::Right);
+        let (length, text_length, across) = if horizontal {
+            (width, text_width, (height - text_height) / 2)
+        } else {
+            (height, text_height, (width - text_width) / 2)
+        };
+        let (start, end) = match self.repeat {
+            Repeat::Loop | Repeat::Once => (length, -text_length),
+            Repeat::Bounce => (0, length - text_length),
+        };
+        return match self.direction {
+            Direction::Left | Direction::Up => (start, end, across),
+            Direction::Right | Direction::Down => (end, start, across),
+        };
+    }
+
+    /// Number of frames needed to go through a `W` x `H` image once
+    pub fn pass_len<const W: usize, const H: usize>(&self) -> u32 {
+        let (start, end, _) = self.course(W as i32, H as i32);
+        return start.abs_diff(end) * self.frames_per_pixel.max(1);
+    }
+
+    /// Whether the text has gone for good at `frame`
+    pub fn is_finished<const W: usize, const H: usize>(&self, frame: u32) -> bool {
+        return self.repeat == Repeat::Once && frame >= self.pass_len::<W, H>();
+    }
+
+    /// Draw the text as it is at `frame` over `image`
+    pub fn draw<const W: usize, const H: usize>(&self, frame: u32, image: &mut Image<W, H>) {
+        let (start, end, _) = self.course(W as i32, H as i32);
+        let distance = start.abs_diff(end);
+        let step = frame / self.frames_per_pixel.max(1);
+        let (moved, pass) = match self.repeat {
+            _ if distance == 0 => (0, 0),
+            Repeat::Loop => (step % distance, step / distance),
+            Repeat::Once => (step.min(distance), 0),
+            Repeat::Bounce => {
+                let moved = step % (2 * distance);
+                (moved.min(2 * distance - moved), step / distance)
+            }
+        };
+        self.draw_moved(moved as i32, pass, frame, image);
+    }
+
+    /// Draw the text `offset` pixels along its course from where it starts,
+    /// whatever the frame, so that it can follow a `timeline::Track`. `frame`
+    /// still drives blinking and rainbows.
+    pub fn draw_at_offset<const W: usize, const H: usize>(
+        &self,
+        offset: i32,
+        frame: u32,
+        image: &mut Image<W, H>,
+    ) {
+        self.draw_moved(offset, 0, frame, image);
+    }
+
+    /// Draw the text `moved` pixels from its start during pass number `pass`
+    fn draw_moved<const W: usize, const H: usize>(
+        &self,
+        moved: i32,
+        pass: u32,
+        frame: u32,
+        image: &mut Image<W, H>,
+    ) {
+        let (start, end, across) = self.course(W as i32, H as i32);
+        let along = start + (end - start).signum() * moved;
+        let position = match self.direction {
+            Direction::Left | Direction::Right => Point::new(along, across),
+            Direction::Up | Direction::Down => Point::new(across, along),
+        };
+        let color = match self.colors.len() {
+            0 => Color::WHITE,
+            len => self.colors[pass as usize % len],
+        };
+        if self.markup {
+            self.draw_markup(frame, position, color, image);
+        } else {
+            let _ = self.font.draw(self.text, position, color.into(), image);
+        }
+    }
+
+    /// Draw the spans of the text one after the other from `position`, in
+    /// `color` unless their style says otherwise
+    fn draw_markup<const W: usize, const H: usize>(
+        &self,
+        frame: u32,
+        mut position: Point,
+        color: Color,
+        image: &mut Image<W, H>,
+    ) {
+        let hidden = (frame / BLINK_FRAMES) % 2 == 1;
+        for span in Markup::new_unchecked(self.text).spans() {
+            let style = span.style;
+            let background = style.background.map(Into::into);
+            if style.rainbow && !(style.blink && hidden) {
+                // Character by character, each one in the colour of the
+                // column where it starts
+                for (i, c) in span.text.char_indices() {
+                    let text = &span.text[i..i + c.len_utf8()];
+                    let column = position.x.rem_euclid(HUE_STEPS as i32) as u32;
+                    let shift = frame % HUE_STEPS as u32 * RAINBOW_SPEED;
+                    let hue = (column * RAINBOW_STEP + shift) % HUE_STEPS as u32;
+                    let color = Some(Color::from_hue(hue as u16).into());
+                    let next = self
+                        .font
+                        .draw_styled(text, position, color, background, image);
+                    position = next.unwrap_or(position);
+                }
+                continue;
+            }
+            let color = if style.blink && hidden {
+                None
+            } else {
+                Some(style.color.unwrap_or(color).into())
+            };
+            let next = self
+                .font
+                .draw_styled(span.text, position, color, background, image);
+            position = next.unwrap_or(position);
+        }
+    }
+
+    /// The text as it is at `frame` over a black image
+    pub fn render<const W: usize, const H: usize>(&self, frame: u32) -> Image<W, H> {
+        let mut image = Image::default();
+        self.draw(frame, &mut image);
+        return image;
+    }
+}
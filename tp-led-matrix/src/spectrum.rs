@@ -0,0 +1,265 @@
+use crate::{Color, Image};
+use micromath::F32Ext;
+
+/// Number of samples analysed for every frame
+pub const WINDOW: usize = 1024;
+
+/// Lowest and highest frequencies shown, in Hz
+const MIN_FREQ: f32 = 60.0;
+const MAX_FREQ: f32 = 16000.0;
+
+/// Colours of the bars, from the bottom row to the top row
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Ramp {
+    /// Green, then yellow and red at the top, like a VU meter
+    Vu,
+    /// Dark red to yellow
+    Fire,
+    /// Blue to magenta through the hue wheel
+    Rainbow,
+    /// The same colour for the whole bar
+    Solid(Color),
+}
+
+impl Ramp {
+    /// Colour of `row` counted from the bottom, between 0 and 7
+    pub fn color(&self, row: usize) -> Color {
+        const VU: [Color; 8] = [
+            Color { r: 0, g: 255, b: 0 },
+            Color { r: 0, g: 255, b: 0 },
+            Color { r: 0, g: 255, b: 0 },
+            Color {
+                r: 128,
+                g: 255,
+                b: 0,
+            },
+            Color {
+                r: 255,
+                g: 255,
+                b: 0,
+            },
+            Color {
+                r: 255,
+                g: 160,
+                b: 0,
+            },
+            Color {
+                r: 255,
+                g: 64,
+                b: 0,
+            },
+            Color { r: 255, g: 0, b: 0 },
+        ];
+        const FIRE: [Color; 8] = [
+            Color { r: 64, g: 0, b: 0 },
+            Color { r: 128, g: 0, b: 0 },
+            Color {
+                r: 192,
+                g: 16,
+                b: 0,
+            },
+            Color {
+                r: 255,
+                g: 32,
+                b: 0,
+            },
+            Color {
+                r: 255,
+                g: 80,
+                b: 0,
+            },
+            Color {
+                r: 255,
+                g: 128,
+                b: 0,
+            },
+            Color {
+                r: 255,
+                g: 192,
+                b: 0,
+            },
+            Color {
+                r: 255,
+                g: 255,
+                b: 64,
+            },
+        ];
+        const RAINBOW: [Color; 8] = [
+            Color { r: 0, g: 0, b: 255 },
+            Color {
+                r: 0,
+                g: 128,
+                b: 255,
+            },
+            Color {
+                r: 0,
+                g: 255,
+                b: 255,
+            },
+            Color { r: 0, g: 255, b: 0 },
+            Color {
+                r: 255,
+                g: 255,
+                b: 0,
+            },
+            Color {
+                r: 255,
+                g: 128,
+                b: 0,
+            },
+            Color { r: 255, g: 0, b: 0 },
+            Color {
+                r: 255,
+                g: 0,
+                b: 255,
+            },
+        ];
+        return match self {
+            Ramp::Vu => VU[row.min(7)],
+            Ramp::Fire => FIRE[row.min(7)],
+            Ramp::Rainbow => RAINBOW[row.min(7)],
+            Ramp::Solid(color) => *color,
+        };
+    }
+}
+
+/// Turns windows of audio samples into 8 bars, one per log-spaced frequency
+/// band, with optional peak-hold dots
+pub struct Visualiser {
+    pub ramp: Ramp,
+    /// Colour of the peak dots, `None` to disable them
+    pub peak_color: Option<Color>,
+    /// Number of frames a peak dot stays before falling
+    pub peak_hold: u32,
+    /// Levels mapped to an empty and to a full bar, in dB relative to a
+    /// full-scale sine
+    pub floor_db: f32,
+    pub ceiling_db: f32,
+    /// Maximum fall of a bar between two frames, in rows
+    pub decay: f32,
+    /// FFT bins `start..end` summed in each band
+    bands: [(usize, usize); 8],
+    window: [f32; WINDOW],
+    twiddles: [(f32, f32); WINDOW / 2],
+    levels: [f32; 8],
+    peaks: [(usize, u32); 8],
+}
+
+impl Visualiser {
+    pub fn new(sample_rate: u32) -> Self {
+        let max_freq = MAX_FREQ.min(sample_rate as f32 / 2.0);
+        let bin =
+            |freq: f32| ((freq * WINDOW as f32 / sample_rate as f32) as usize).clamp(1, WINDOW / 2);
+        let mut bands = [(0, 0); 8];
+        let mut start = bin(MIN_FREQ);
+        for (k, band) in bands.iter_mut().enumerate() {
+            let edge = MIN_FREQ * (max_freq / MIN_FREQ).powf((k + 1) as f32 / 8.0);
+            // Every band gets at least one bin, which matters for the low ones
+            let end = bin(edge).max(start + 1).min(WINDOW / 2);
+            *band = (start.min(end - 1), end);
+            start = end;
+        }
+
+        let mut window = [0.0; WINDOW];
+        for (i, w) in window.iter_mut().enumerate() {
+            // Hann window
+            *w = 0.5 - 0.5 * (2.0 * core::f32::consts::PI * i as f32 / WINDOW as f32).cos();
+        }
+        let mut twiddles = [(0.0, 0.0); WINDOW / 2];
+        for (i, t) in twiddles.iter_mut().enumerate() {
+            let angle = -2.0 * core::f32::consts::PI * i as f32 / WINDOW as f32;
+            *t = (angle.cos(), angle.sin());
+        }
+
+        return Visualiser {
+            ramp: Ramp::Vu,
+            peak_color: Some(Color {
+                r: 255,
+                g: 255,
+                b: 255,
+            }),
+            peak_hold: 15,
+            floor_db: -60.0,
+            ceiling_db: -6.0,
+            decay: 0.5,
+            bands,
+            window,
+            twiddles,
+            levels: [0.0; 8],
+            peaks: [(0, 0); 8],
+        };
+    }
+
+    /// Analyse the last `WINDOW` samples, in the -1.0..1.0 range, and draw
+    /// the resulting bars
+    pub fn process(&mut self, samples: &[f32; WINDOW]) -> Image {
+        let spectrum = self.fft(samples);
+
+        let mut image = Image::default();
+        for (band, &(start, end)) in self.bands.iter().enumerate() {
+            let power: f32 = spectrum[start..end].iter().sum();
+            let db = 10.0 * power.max(1e-12).log10();
+            let level =
+                ((db - self.floor_db) / (self.ceiling_db - self.floor_db) * 8.0).clamp(0.0, 8.0);
+            self.levels[band] = level.max(self.levels[band] - self.decay);
+            let height = (self.levels[band] + 0.5) as usize;
+
+            let (peak, age) = &mut self.peaks[band];
+            if height >= *peak {
+                *peak = height;
+                *age = 0;
+            } else if *age < self.peak_hold {
+                *age += 1;
+            } else {
+                *peak -= 1;
+            }
+
+            for row in 0..height {
+                image[(7 - row, band)] = self.ramp.color(row);
+            }
+            if let Some(color) = self.peak_color {
+                if *peak > 0 {
+                    image[(8 - *peak, band)] = color;
+                }
+            }
+        }
+        return image;
+    }
+
+    /// Power of the first half of the spectrum, normalised so that a
+    /// full-scale sine gives 1.0 in its bin
+    fn fft(&self, samples: &[f32; WINDOW]) -> [f32; WINDOW / 2] {
+        let mut data = [(0.0f32, 0.0f32); WINDOW];
+        // Bit-reversed copy of the windowed samples
+        let bits = WINDOW.trailing_zeros();
+        for (i, &sample) in samples.iter().enumerate() {
+            let j = i.reverse_bits() >> (usize::BITS - bits);
+            data[j] = (sample * self.window[i], 0.0);
+        }
+
+        let mut size = 2;
+        while size <= WINDOW {
+            let stride = WINDOW / size;
+            for start in (0..WINDOW).step_by(size) {
+                for k in 0..size / 2 {
+                    let (wr, wi) = self.twiddles[k * stride];
+                    let (ar, ai) = data[start + k];
+                    let (br, bi) = data[start + k + size / 2];
+                    let (tr, ti) = (br * wr - bi * wi, br * wi + bi * wr);
+                    data[start + k] = (ar + tr, ai + ti);
+                    data[start + k + size / 2] = (ar - tr, ai - ti);
+                }
+            }
+            size *= 2;
+        }
+
+        // The Hann window halves the amplitude of a sine, which spreads over
+        // two half-spectra
+        let scale = 4.0 / WINDOW as f32;
+        let mut power = [0.0; WINDOW / 2];
+        for (p, &(re, im)) in power.iter_mut().zip(data.iter()) {
+            *p = (re * re + im * im) * scale * scale;
+        }
+        return power;
+    }
+}
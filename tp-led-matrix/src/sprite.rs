@@ -0,0 +1,194 @@
+use crate::{Color, Image, Instant};
+
+/// Picture made of palette indices, row by row, 0 being transparent and `n`
+/// the `n`-th colour of the palette it is drawn with
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Bitmap<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: &'a [u8],
+}
+
+impl<'a> Bitmap<'a> {
+    /// Panics, at compile time in a constant, if `width` is 0 or `pixels`
+    /// does not hold `width` x `height` indices
+    pub const fn new(width: usize, height: usize, pixels: &'a [u8]) -> Self {
+        assert!(width > 0 && pixels.len() == width * height);
+        return Bitmap {
+            width,
+            height,
+            pixels,
+        };
+    }
+
+    /// Palette index at column `x` and row `y`, 0 outside of the bitmap
+    pub fn index(&self, x: i32, y: i32) -> u8 {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return 0;
+        }
+        return self.pixels[y as usize * self.width + x as usize];
+    }
+
+    /// Draw with its top left corner at `(x, y)` of `image`, which may be
+    /// outside of it, leaving the transparent pixels alone
+    pub fn draw<const W: usize, const H: usize>(
+        &self,
+        palette: &[Color],
+        x: i32,
+        y: i32,
+        image: &mut Image<W, H>,
+    ) {
+        if self.width == 0 {
+            return;
+        }
+        for (i, &index) in self.pixels.iter().enumerate() {
+            let (px, py) = (x + (i % self.width) as i32, y + (i / self.width) as i32);
+            let Some(&color) = index.checked_sub(1).and_then(|i| palette.get(i as usize)) else {
+                continue;
+            };
+            if px >= 0 && py >= 0 {
+                if let Some(pixel) = image.get_mut(px as usize, py as usize) {
+                    *pixel = color;
+                }
+            }
+        }
+    }
+}
+
+/// Animated picture placed anywhere in the world, even partly or wholly out
+/// of sight. Positions are those of the top left corner, in pixels.
+#[derive(Copy, Clone, Debug)]
+pub struct Sprite<'a> {
+    pub frames: &'a [Bitmap<'a>],
+    pub palette: &'a [Color],
+    pub x: i32,
+    pub y: i32,
+    /// Sprites with a higher `z` are drawn over the others
+    pub z: i8,
+    /// Index of the frame shown
+    pub frame: usize,
+    /// Time each frame is shown for by `animate()`, 0 to leave `frame` alone
+    pub frame_ms: u32,
+    pub visible: bool,
+}
+
+impl<'a> Sprite<'a> {
+    /// Visible sprite at `(x, y)`, showing its first frame
+    pub const fn new(frames: &'a [Bitmap<'a>], palette: &'a [Color], x: i32, y: i32) -> Self {
+        return Sprite {
+            frames,
+            palette,
+            x,
+            y,
+            z: 0,
+            frame: 0,
+            frame_ms: 0,
+            visible: true,
+        };
+    }
+
+    /// Frame shown, or `None` if there are none
+    pub fn bitmap(&self) -> Option<&'a Bitmap<'a>> {
+        if self.frames.is_empty() {
+            return None;
+        }
+        return Some(&self.frames[self.frame % self.frames.len()]);
+    }
+
+    /// Show the frame due at `t`, going through them in a loop
+    pub fn animate(&mut self, t: Instant) {
+        if self.frame_ms != 0 && !self.frames.is_empty() {
+            let frame = t.ticks() / self.frame_ms as u64 % self.frames.len() as u64;
+            self.frame = frame as usize;
+        }
+    }
+
+    /// Move by `dx` and `dy` pixels
+    pub fn shift(&mut self, dx: i32, dy: i32) {
+        self.x += dx;
+        self.y += dy;
+    }
+
+    /// Top left corner, width and height of the frame shown
+    pub fn bounds(&self) -> (i32, i32, usize, usize) {
+        return match self.bitmap() {
+            Some(bitmap) => (self.x, self.y, bitmap.width, bitmap.height),
+            None => (self.x, self.y, 0, 0),
+        };
+    }
+
+    /// Whether the pixel at `(x, y)` of the world is covered by the sprite
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        return self.visible
+            && self
+                .bitmap()
+                .is_some_and(|bitmap| bitmap.index(x - self.x, y - self.y) != 0);
+    }
+
+    /// Whether the bounding boxes of both sprites overlap, transparent pixels
+    /// included
+    pub fn overlaps(&self, other: &Sprite) -> bool {
+        let (x, y, w, h) = self.bounds();
+        let (ox, oy, ow, oh) = other.bounds();
+        return self.visible
+            && other.visible
+            && x < ox + ow as i32
+            && ox < x + w as i32
+            && y < oy + oh as i32
+            && oy < y + h as i32;
+    }
+
+    /// Whether some pixel is covered by both sprites
+    pub fn collides(&self, other: &Sprite) -> bool {
+        if !self.overlaps(other) {
+            return false;
+        }
+        let (x, y, w, h) = self.bounds();
+        for py in y..y + h as i32 {
+            for px in x..x + w as i32 {
+                if self.contains(px, py) && other.contains(px, py) {
+                    return true;
+                }
+            }
+        }
+        return false;
+    }
+
+    /// Draw into `image` if visible, `origin` being the point of the world
+    /// at its top left corner
+    pub fn draw<const W: usize, const H: usize>(
+        &self,
+        origin: (i32, i32),
+        image: &mut Image<W, H>,
+    ) {
+        if let (true, Some(bitmap)) = (self.visible, self.bitmap()) {
+            bitmap.draw(self.palette, self.x - origin.0, self.y - origin.1, image);
+        }
+    }
+}
+
+/// Draw `sprites` from the lowest `z` to the highest, those with the same
+/// `z` in the order of the slice, `origin` being the point of the world at
+/// the top left corner of `image`
+pub fn draw_sprites<const W: usize, const H: usize>(
+    sprites: &[Sprite],
+    origin: (i32, i32),
+    image: &mut Image<W, H>,
+) {
+    // Next sprite after `last` in drawing order, found again every time so
+    // that nothing needs to be sorted or allocated
+    let mut last: Option<(i8, usize)> = None;
+    loop {
+        let next = sprites
+            .iter()
+            .enumerate()
+            .map(|(i, sprite)| (sprite.z, i))
+            .filter(|&key| last.is_none_or(|last| key > last))
+            .min();
+        let Some((_, i)) = next else {
+            return;
+        };
+        sprites[i].draw(origin, image);
+        last = next;
+    }
+}
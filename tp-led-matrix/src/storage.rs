@@ -0,0 +1,104 @@
+use crate::correction::Profile;
+use stm32l4xx_hal::flash::{self, FlashPage, WriteErase, CR, KEYR, SR};
+
+/// Last page of the 1 MB of flash, holding the panel profile. It is left out
+/// of the `FLASH` region of `memory.x` so that the firmware is never placed
+/// there.
+const PROFILE_PAGE: FlashPage = FlashPage(511);
+/// Page before it, holding the playlist, also left out of `memory.x`
+const PLAYLIST_PAGE: FlashPage = FlashPage(510);
+const PAGE_SIZE: usize = 2048;
+/// Marks a page written by `Storage::save()`, followed by the length of the
+/// text on two bytes and two bytes of padding
+const MAGIC: [u8; 4] = *b"TPLM";
+/// Marks a page written by `Storage::save_profile()`, with the same header
+const PROFILE_MAGIC: [u8; 4] = *b"TPPF";
+const HEADER_LEN: usize = 8;
+
+/// Longest text which can be stored
+pub const CAPACITY: usize = PAGE_SIZE - HEADER_LEN;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Longer than `CAPACITY`
+    TooLong,
+    Flash(flash::Error),
+}
+
+impl From<flash::Error> for Error {
+    fn from(error: flash::Error) -> Self {
+        return Error::Flash(error);
+    }
+}
+
+/// Panel profile and text, such as the playlist, kept in two pages of flash
+/// across resets
+pub struct Storage {
+    keyr: KEYR,
+    sr: SR,
+    cr: CR,
+}
+
+impl Storage {
+    /// Take the flash registers left over once the clocks are set up
+    pub fn new(keyr: KEYR, sr: SR, cr: CR) -> Self {
+        return Storage { keyr, sr, cr };
+    }
+
+    /// Bytes saved last in `page` with `magic`, or `None` if nothing valid
+    /// was ever saved there
+    fn read(&self, page: FlashPage, magic: [u8; 4]) -> Option<&[u8]> {
+        // The flash is mapped in memory and only changed by `write()`, which
+        // cannot be called while the returned bytes borrow `self`
+        let page =
+            unsafe { core::slice::from_raw_parts(page.to_address() as *const u8, PAGE_SIZE) };
+        if page[..4] != magic {
+            return None;
+        }
+        let len = u16::from_le_bytes([page[4], page[5]]) as usize;
+        return page.get(HEADER_LEN..HEADER_LEN + len);
+    }
+
+    /// Replace the content of `page` by `magic` and `data`
+    fn write(&mut self, page: FlashPage, magic: [u8; 4], data: &[u8]) -> Result<(), Error> {
+        if data.len() > CAPACITY {
+            return Err(Error::TooLong);
+        }
+        let address = page.to_address();
+        let mut flash = self.keyr.unlock_flash(&mut self.sr, &mut self.cr)?;
+        flash.erase_page(page)?;
+        let mut header = [0; HEADER_LEN];
+        header[..4].copy_from_slice(&magic);
+        header[4..6].copy_from_slice(&(data.len() as u16).to_le_bytes());
+        flash.write(address, &header)?;
+        // Written by double words, as the flash requires
+        for (i, chunk) in data.chunks(8).enumerate() {
+            let mut double_word = [0xff; 8];
+            double_word[..chunk.len()].copy_from_slice(chunk);
+            flash.write(address + HEADER_LEN + 8 * i, &double_word)?;
+        }
+        return Ok(());
+    }
+
+    /// Text saved last, or `None` if nothing valid was ever saved. It is
+    /// read in place, so it borrows the storage until it is dropped.
+    pub fn load(&self) -> Option<&str> {
+        return core::str::from_utf8(self.read(PLAYLIST_PAGE, MAGIC)?).ok();
+    }
+
+    /// Replace the saved text by `text`
+    pub fn save(&mut self, text: &str) -> Result<(), Error> {
+        return self.write(PLAYLIST_PAGE, MAGIC, text.as_bytes());
+    }
+
+    /// Profile saved last, or `None` if none was ever saved or it cannot be
+    /// read by this firmware
+    pub fn load_profile(&self) -> Option<Profile> {
+        return Profile::from_bytes(self.read(PROFILE_PAGE, PROFILE_MAGIC)?).ok();
+    }
+
+    /// Replace the saved profile by `profile`
+    pub fn save_profile(&mut self, profile: &Profile) -> Result<(), Error> {
+        return self.write(PROFILE_PAGE, PROFILE_MAGIC, &profile.to_bytes());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::sprite::{draw_sprites, Bitmap, Sprite};
+use crate::{Color, Image};
+
+/// Grid of tiles making a world larger than the panel, seen through a
+/// `Camera`. Cells hold `n` to show `tiles[n - 1]`, or 0 to stay empty. All
+/// the tiles are drawn with the same palette and should have the same size.
+#[derive(Copy, Clone, Debug)]
+pub struct TileMap<'a> {
+    pub tiles: &'a [Bitmap<'a>],
+    pub palette: &'a [Color],
+    pub tile_width: usize,
+    pub tile_height: usize,
+    /// Cells across
+    pub columns: usize,
+    /// Cells row by row
+    pub cells: &'a [u8],
+    /// Colour of the empty cells, of the transparent pixels and of whatever
+    /// lies beyond the map
+    pub background: Color,
+}
+
+impl<'a> TileMap<'a> {
+    /// Map of `tile_width` x `tile_height` tiles on a black background.
+    /// Panics, at compile time in a constant, if `cells` is not made of
+    /// whole rows of `columns` cells.
+    pub const fn new(
+        tiles: &'a [Bitmap<'a>],
+        palette: &'a [Color],
+        tile_width: usize,
+        tile_height: usize,
+        columns: usize,
+        cells: &'a [u8],
+    ) -> Self {
+        assert!(columns != 0 && cells.len().is_multiple_of(columns));
+        return TileMap {
+            tiles,
+            palette,
+            tile_width,
+            tile_height,
+            columns,
+            cells,
+            background: Color::BLACK,
+        };
+    }
+
+    /// Cells down
+    pub fn rows(&self) -> usize {
+        return self.cells.len() / self.columns;
+    }
+
+    /// Width and height of the whole map, in pixels
+    pub fn size(&self) -> (usize, usize) {
+        return (
+            self.columns * self.tile_width,
+            self.rows() * self.tile_height,
+        );
+    }
+
+    /// Cell at `column` and `row`, 0 outside of the map
+    pub fn cell(&self, column: usize, row: usize) -> u8 {
+        if column >= self.columns || row >= self.rows() {
+            return 0;
+        }
+        return self.cells[row * self.columns + column];
+    }
+
+    /// Cell under the pixel at `(x, y)` of the world, 0 outside of the map
+    pub fn cell_at(&self, x: i32, y: i32) -> u8 {
+        if x < 0 || y < 0 || self.tile_width == 0 || self.tile_height == 0 {
+            return 0;
+        }
+        return self.cell(x as usize / self.tile_width, y as usize / self.tile_height);
+    }
+
+    /// Palette index of the pixel at `(x, y)` of the world, 0 where nothing
+    /// is drawn
+    pub fn index(&self, x: i32, y: i32) -> u8 {
+        let Some(tile) = self.cell_at(x, y).checked_sub(1) else {
+            return 0;
+        };
+        return match self.tiles.get(tile as usize) {
+            Some(bitmap) => bitmap.index(
+                x.rem_euclid(self.tile_width as i32),
+                y.rem_euclid(self.tile_height as i32),
+            ),
+            None => 0,
+        };
+    }
+
+    /// Whether a tile covers the pixel at `(x, y)` of the world, which makes
+    /// it a wall for the sprites
+    pub fn is_solid(&self, x: i32, y: i32) -> bool {
+        return self.index(x, y) != 0;
+    }
+
+    /// Whether some pixel of `sprite` is over a solid pixel of the map
+    pub fn collides(&self, sprite: &Sprite) -> bool {
+        let (x, y, w, h) = sprite.bounds();
+        for py in y..y + h as i32 {
+            for px in x..x + w as i32 {
+                if sprite.contains(px, py) && self.is_solid(px, py) {
+                    return true;
+                }
+            }
+        }
+        return false;
+    }
+
+    /// Fill `image` with the part of the map whose top left corner is
+    /// `origin`
+    pub fn draw<const W: usize, const H: usize>(
+        &self,
+        origin: (i32, i32),
+        image: &mut Image<W, H>,
+    ) {
+        for ((x, y), pixel) in image.pixels_mut() {
+            let index = self.index(origin.0 + x as i32, origin.1 + y as i32);
+            *pixel = match index.checked_sub(1) {
+                Some(i) => self
+                    .palette
+                    .get(i as usize)
+                    .copied()
+                    .unwrap_or(self.background),
+                None => self.background,
+            };
+        }
+    }
+}
+
+/// Part of the world shown in a `W` x `H` image, its top left corner being
+/// at `(x, y)`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Camera<const W: usize = 8, const H: usize = 8> {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl<const W: usize, const H: usize> Camera<W, H> {
+    pub const fn new(x: i32, y: i32) -> Self {
+        return Camera { x, y };
+    }
+
+    pub fn origin(&self) -> (i32, i32) {
+        return (self.x, self.y);
+    }
+
+    /// Move so that nothing beyond `map` is shown, unless it is smaller than
+    /// the image, in which case it stays at the top left corner
+    pub fn clamp(&mut self, map: &TileMap) {
+        let (width, height) = map.size();
+        self.x = self.x.min(width as i32 - W as i32).max(0);
+        self.y = self.y.min(height as i32 - H as i32).max(0);
+    }
+
+    /// Centre on `sprite`, staying within `map`
+    pub fn follow(&mut self, sprite: &Sprite, map: &TileMap) {
+        let (x, y, w, h) = sprite.bounds();
+        self.x = x + w as i32 / 2 - W as i32 / 2;
+        self.y = y + h as i32 / 2 - H as i32 / 2;
+        self.clamp(map);
+    }
+
+    /// Whether any pixel of `sprite` is in sight
+    pub fn sees(&self, sprite: &Sprite) -> bool {
+        let (x, y, w, h) = sprite.bounds();
+        return sprite.visible
+            && x < self.x + W as i32
+            && self.x < x + w as i32
+            && y < self.y + H as i32
+            && self.y < y + h as i32;
+    }
+
+    /// The map with `sprites` over it, as seen from here
+    pub fn render(&self, map: &TileMap, sprites: &[Sprite]) -> Image<W, H> {
+        let mut image = Image::default();
+        map.draw(self.origin(), &mut image);
+        draw_sprites(sprites, self.origin(), &mut image);
+        return image;
+    }
+}
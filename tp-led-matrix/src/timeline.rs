@@ -0,0 +1,267 @@
+use crate::{Color, Duration, Instant};
+use core::f32::consts::PI;
+use micromath::F32Ext;
+
+/// How a value goes from one keyframe to the next, as a function of the
+/// time elapsed from 0.0 to 1.0 giving the progress from 0.0 to 1.0
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    /// Quadratic, starting slowly
+    EaseIn,
+    /// Quadratic, ending slowly
+    EaseOut,
+    /// Quadratic, starting and ending slowly
+    EaseInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    /// Half a cosine wave, starting and ending slowly
+    Sine,
+    /// Falling onto the end value and bouncing back a few times
+    Bounce,
+    /// Keeping the start value until the end
+    Step,
+}
+
+impl Easing {
+    /// Progress at `t`, both from 0.0 to 1.0, `t` being clamped
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        return match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut if t < 0.5 => 2.0 * t * t,
+            Easing::EaseInOut => 1.0 - 2.0 * (1.0 - t) * (1.0 - t),
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t) * (1.0 - t) * (1.0 - t),
+            Easing::CubicInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::CubicInOut => 1.0 - 4.0 * (1.0 - t) * (1.0 - t) * (1.0 - t),
+            Easing::Sine => (1.0 - (PI * t).cos()) / 2.0,
+            Easing::Bounce => bounce(t),
+            Easing::Step if t < 1.0 => 0.0,
+            Easing::Step => 1.0,
+        };
+    }
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        return Easing::Linear;
+    }
+}
+
+/// Parabolic bounces of decreasing height, as in Robert Penner's easings
+fn bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    return if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    };
+}
+
+/// Values which can be animated, by going part of the way from one to another
+pub trait Interpolate: Copy {
+    /// `a` at 0.0, `b` at 1.0, rounded to the nearest value in between
+    fn interpolate(a: Self, b: Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(a: Self, b: Self, t: f32) -> Self {
+        return a + (b - a) * t;
+    }
+}
+
+impl Interpolate for i32 {
+    fn interpolate(a: Self, b: Self, t: f32) -> Self {
+        return (a as f32 + (b - a) as f32 * t).round() as i32;
+    }
+}
+
+impl Interpolate for u8 {
+    fn interpolate(a: Self, b: Self, t: f32) -> Self {
+        return (a as f32 + (b as f32 - a as f32) * t)
+            .round()
+            .clamp(0.0, 255.0) as u8;
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(a: Self, b: Self, t: f32) -> Self {
+        return Color::lerp(a, b, u8::interpolate(0, 255, t));
+    }
+}
+
+/// Points, such as the position of a sprite
+impl Interpolate for (i32, i32) {
+    fn interpolate(a: Self, b: Self, t: f32) -> Self {
+        return (i32::interpolate(a.0, b.0, t), i32::interpolate(a.1, b.1, t));
+    }
+}
+
+/// A value reached at a given time, `easing` telling how it is reached from
+/// the previous keyframe
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Keyframe<T> {
+    pub at_ms: u32,
+    pub value: T,
+    pub easing: Easing,
+}
+
+impl<T> Keyframe<T> {
+    pub const fn new(at_ms: u32, value: T, easing: Easing) -> Self {
+        return Keyframe {
+            at_ms,
+            value,
+            easing,
+        };
+    }
+}
+
+/// Keyframes of a value, sorted by time. Before the first keyframe the value
+/// is the first one, and after the last keyframe it stays the last one,
+/// unless the track loops.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Track<'a, T> {
+    pub keys: &'a [Keyframe<T>],
+    /// Start again once the last keyframe is reached
+    pub looping: bool,
+}
+
+impl<'a, T: Interpolate> Track<'a, T> {
+    pub const fn new(keys: &'a [Keyframe<T>]) -> Self {
+        return Track {
+            keys,
+            looping: false,
+        };
+    }
+
+    /// Track without keyframes, which has no value
+    pub const fn empty() -> Self {
+        return Track::new(&[]);
+    }
+
+    /// Time of the last keyframe
+    pub fn duration_ms(&self) -> u32 {
+        return self.keys.last().map_or(0, |key| key.at_ms);
+    }
+
+    /// Value `elapsed` after the start of the track, or `None` without
+    /// keyframes
+    pub fn value_at(&self, elapsed: Duration) -> Option<T> {
+        let (first, last) = (self.keys.first()?, self.keys.last()?);
+        let t_ms = match self.duration_ms() {
+            duration if self.looping && duration != 0 => elapsed.ticks() % duration as u64,
+            _ => elapsed.ticks(),
+        };
+        let Some(next) = self.keys.iter().position(|key| key.at_ms as u64 > t_ms) else {
+            return Some(last.value);
+        };
+        if next == 0 {
+            return Some(first.value);
+        }
+        let (from, to) = (&self.keys[next - 1], &self.keys[next]);
+        let t = (t_ms - from.at_ms as u64) as f32 / (to.at_ms - from.at_ms) as f32;
+        return Some(T::interpolate(from.value, to.value, to.easing.apply(t)));
+    }
+}
+
+/// What a `Timeline` gives at a point in time
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct State {
+    /// Top left corner of what moves, such as a sprite
+    pub position: (i32, i32),
+    pub color: Color,
+    /// Out of 255, to scale the colours with
+    pub brightness: u8,
+    /// Pixels moved by a scrolling text from its start
+    pub offset: i32,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        return State {
+            position: (0, 0),
+            color: Color::WHITE,
+            brightness: 255,
+            offset: 0,
+        };
+    }
+}
+
+/// Properties animated together from `start`. Properties without keyframes
+/// keep the value of `State::default()`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Timeline<'a> {
+    pub position: Track<'a, (i32, i32)>,
+    pub color: Track<'a, Color>,
+    pub brightness: Track<'a, u8>,
+    pub offset: Track<'a, i32>,
+    pub start: Instant,
+    /// Start every track again once the longest one is over
+    pub looping: bool,
+}
+
+impl<'a> Timeline<'a> {
+    /// Timeline without keyframes starting at `start`
+    pub const fn new(start: Instant) -> Self {
+        return Timeline {
+            position: Track::empty(),
+            color: Track::empty(),
+            brightness: Track::empty(),
+            offset: Track::empty(),
+            start,
+            looping: false,
+        };
+    }
+
+    /// Time of the last keyframe of all the tracks
+    pub fn duration_ms(&self) -> u32 {
+        return self
+            .position
+            .duration_ms()
+            .max(self.color.duration_ms())
+            .max(self.brightness.duration_ms())
+            .max(self.offset.duration_ms());
+    }
+
+    /// Time elapsed since the start at `t`, zero before it
+    fn elapsed(&self, t: Instant) -> Duration {
+        return t
+            .checked_duration_since(self.start)
+            .unwrap_or(Duration::from_ticks(0));
+    }
+
+    /// Whether every track has reached its last keyframe at `t`
+    pub fn is_finished(&self, t: Instant) -> bool {
+        return !self.looping && self.elapsed(t).ticks() >= self.duration_ms() as u64;
+    }
+
+    /// Properties at `t`
+    pub fn at(&self, t: Instant) -> State {
+        let mut elapsed = self.elapsed(t);
+        if self.looping && self.duration_ms() != 0 {
+            elapsed = Duration::from_ticks(elapsed.ticks() % self.duration_ms() as u64);
+        }
+        let default = State::default();
+        return State {
+            position: self.position.value_at(elapsed).unwrap_or(default.position),
+            color: self.color.value_at(elapsed).unwrap_or(default.color),
+            brightness: self
+                .brightness
+                .value_at(elapsed)
+                .unwrap_or(default.brightness),
+            offset: self.offset.value_at(elapsed).unwrap_or(default.offset),
+        };
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{Color, Image};
+
+/// Clockwise rotation by a multiple of a quarter turn
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Rotation {
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+/// What happens to the pixels pushed out by a shift
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Edge {
+    /// They are lost, and the uncovered pixels get this colour
+    Fill(Color),
+    /// They come back on the other side
+    Wrap,
+}
+
+/// How the panel is mounted. The image is mirrored first (left and right
+/// swapped) if `mirrored` is set, then rotated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Orientation {
+    pub rotation: Rotation,
+    pub mirrored: bool,
+}
+
+impl Orientation {
+    pub const NORMAL: Orientation = Orientation {
+        rotation: Rotation::Deg0,
+        mirrored: false,
+    };
+
+    /// Position in the source image of the pixel shown at (`row`, `col`)
+    pub fn source(&self, row: usize, col: usize) -> (usize, usize) {
+        let (row, col) = match self.rotation {
+            Rotation::Deg0 => (row, col),
+            Rotation::Deg90 => (7 - col, row),
+            Rotation::Deg180 => (7 - row, 7 - col),
+            Rotation::Deg270 => (col, 7 - row),
+        };
+        if self.mirrored {
+            return (row, 7 - col);
+        }
+        return (row, col);
+    }
+
+    /// Row `row` of `image` as shown on a panel mounted this way
+    pub fn row(&self, image: &Image, row: usize) -> [Color; 8] {
+        let mut pixels = [Color::default(); 8];
+        for (col, pixel) in pixels.iter_mut().enumerate() {
+            *pixel = image[self.source(row, col)];
+        }
+        return pixels;
+    }
+
+    /// The whole image as shown on a panel mounted this way
+    pub fn apply(&self, image: &Image) -> Image {
+        let mut result = Image::default();
+        for row in 0..8 {
+            for col in 0..8 {
+                result[(row, col)] = image[self.source(row, col)];
+            }
+        }
+        return result;
+    }
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        return Orientation::NORMAL;
+    }
+}
+
+impl Image {
+    pub fn rotated(&self, rotation: Rotation) -> Image {
+        return Orientation {
+            rotation,
+            mirrored: false,
+        }
+        .apply(self);
+    }
+
+    /// Left and right swapped
+    pub fn flipped_horizontal(&self) -> Image {
+        return Orientation {
+            rotation: Rotation::Deg0,
+            mirrored: true,
+        }
+        .apply(self);
+    }
+
+    /// Top and bottom swapped
+    pub fn flipped_vertical(&self) -> Image {
+        return Orientation {
+            rotation: Rotation::Deg180,
+            mirrored: true,
+        }
+        .apply(self);
+    }
+
+    /// Rows and columns swapped
+    pub fn transposed(&self) -> Image {
+        return Orientation {
+            rotation: Rotation::Deg270,
+            mirrored: true,
+        }
+        .apply(self);
+    }
+
+    /// Moved `dx` columns to the right and `dy` rows down, negative values
+    /// moving left and up
+    pub fn shifted(&self, dx: i32, dy: i32, edge: Edge) -> Image {
+        let mut result = Image::default();
+        for row in 0..8 {
+            for col in 0..8 {
+                let (src_row, src_col) = (row as i32 - dy, col as i32 - dx);
+                result[(row, col)] = match edge {
+                    Edge::Wrap => {
+                        self[(
+                            src_row.rem_euclid(8) as usize,
+                            src_col.rem_euclid(8) as usize,
+                        )]
+                    }
+                    Edge::Fill(color) => {
+                        if (0..8).contains(&src_row) && (0..8).contains(&src_col) {
+                            self[(src_row as usize, src_col as usize)]
+                        } else {
+                            color
+                        }
+                    }
+                };
+            }
+        }
+        return result;
+    }
+
+    pub fn rotate(&mut self, rotation: Rotation) {
+        *self = self.rotated(rotation);
+    }
+
+    pub fn flip_horizontal(&mut self) {
+        *self = self.flipped_horizontal();
+    }
+
+    pub fn flip_vertical(&mut self) {
+        *self = self.flipped_vertical();
+    }
+
+    pub fn transpose(&mut self) {
+        *self = self.transposed();
+    }
+
+    pub fn shift(&mut self, dx: i32, dy: i32, edge: Edge) {
+        *self = self.shifted(dx, dy, edge);
+    }
+}
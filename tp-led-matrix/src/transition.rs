@@ -0,0 +1,358 @@
+use crate::scroller::Direction;
+use crate::timeline::Easing;
+use crate::{Color, Image, PanelImage};
+use core::fmt;
+use core::str::FromStr;
+
+/// Why a string is not a transition
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    UnknownKind,
+    /// Not a number of milliseconds after the `:`
+    BadDuration,
+}
+
+/// How the new image replaces the one shown
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// At once
+    Cut,
+    /// Every pixel fading from the old colour to the new one
+    Crossfade,
+    /// The new image uncovered by an edge going in the given direction
+    Wipe(Direction),
+    /// The new image moving in the given direction over the old one
+    Slide(Direction),
+    /// The new image pushing the old one out in the given direction
+    Push(Direction),
+    /// Pixels changing one by one in a scattered order
+    Dissolve,
+    /// The new image uncovered by a circle growing from the centre
+    Iris,
+}
+
+impl Kind {
+    /// Pixel at column `x` and row `y` of the image shown at `progress`,
+    /// from 0 (all `from`) to 255 (all `to`)
+    pub fn pixel<const W: usize, const H: usize>(
+        &self,
+        from: &Image<W, H>,
+        to: &Image<W, H>,
+        x: usize,
+        y: usize,
+        progress: u8,
+    ) -> Color {
+        let (old, new) = (from[(y, x)], to[(y, x)]);
+        // Columns or rows covered by the new image so far
+        let covered = |len: usize| (progress as usize * len).div_ceil(255);
+        return match *self {
+            Kind::Cut => new,
+            Kind::Crossfade => Color::lerp(old, new, progress),
+            Kind::Wipe(direction) => {
+                let uncovered = match direction {
+                    Direction::Left => x >= W - covered(W),
+                    Direction::Right => x < covered(W),
+                    Direction::Up => y >= H - covered(H),
+                    Direction::Down => y < covered(H),
+                };
+                if uncovered {
+                    new
+                } else {
+                    old
+                }
+            }
+            Kind::Slide(direction) => {
+                let (cw, ch) = (covered(W), covered(H));
+                let source = match direction {
+                    Direction::Left => (x >= W - cw).then(|| (x + cw - W, y)),
+                    Direction::Right => (x < cw).then(|| (x + W - cw, y)),
+                    Direction::Up => (y >= H - ch).then(|| (x, y + ch - H)),
+                    Direction::Down => (y < ch).then(|| (x, y + H - ch)),
+                };
+                match source {
+                    Some((x, y)) => to[(y, x)],
+                    None => old,
+                }
+            }
+            Kind::Push(direction) => {
+                // Position in the two images put side by side, the old one
+                // first in the direction of movement
+                let (cw, ch) = (covered(W), covered(H));
+                match direction {
+                    Direction::Left => match x + cw {
+                        x if x < W => from[(y, x)],
+                        x => to[(y, x - W)],
+                    },
+                    Direction::Right => match x + W - cw {
+                        x if x < W => to[(y, x)],
+                        x => from[(y, x - W)],
+                    },
+                    Direction::Up => match y + ch {
+                        y if y < H => from[(y, x)],
+                        y => to[(y - H, x)],
+                    },
+                    Direction::Down => match y + H - ch {
+                        y if y < H => to[(y, x)],
+                        y => from[(y - H, x)],
+                    },
+                }
+            }
+            Kind::Dissolve => {
+                if threshold(x, y) < progress {
+                    new
+                } else {
+                    old
+                }
+            }
+            Kind::Iris => {
+                // In half pixels from the centre of the image, the circle
+                // reaching the corners at the end
+                let dx = (2 * x + 1) as i32 - W as i32;
+                let dy = (2 * y + 1) as i32 - H as i32;
+                let distance = (dx * dx + dy * dy) as u32;
+                let radius = (W * W + H * H) as u32;
+                if distance * 255 < radius * progress as u32 {
+                    new
+                } else {
+                    old
+                }
+            }
+        };
+    }
+
+    /// Draw in `image` what is shown at `progress`, from 0 (all `from`) to
+    /// 255 (all `to`)
+    pub fn blend<const W: usize, const H: usize>(
+        &self,
+        from: &Image<W, H>,
+        to: &Image<W, H>,
+        progress: u8,
+        image: &mut Image<W, H>,
+    ) {
+        for ((x, y), pixel) in image.pixels_mut() {
+            *pixel = self.pixel(from, to, x, y, progress);
+        }
+    }
+
+    /// Number used on the serial line
+    pub fn to_byte(&self) -> u8 {
+        let direction = |direction| match direction {
+            Direction::Left => 0,
+            Direction::Right => 1,
+            Direction::Up => 2,
+            Direction::Down => 3,
+        };
+        return match *self {
+            Kind::Cut => 0,
+            Kind::Crossfade => 1,
+            Kind::Wipe(d) => 2 + direction(d),
+            Kind::Slide(d) => 6 + direction(d),
+            Kind::Push(d) => 10 + direction(d),
+            Kind::Dissolve => 14,
+            Kind::Iris => 15,
+        };
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Kind> {
+        const DIRECTIONS: [Direction; 4] = [
+            Direction::Left,
+            Direction::Right,
+            Direction::Up,
+            Direction::Down,
+        ];
+        let direction = DIRECTIONS[(byte.wrapping_sub(2) % 4) as usize];
+        return match byte {
+            0 => Some(Kind::Cut),
+            1 => Some(Kind::Crossfade),
+            2..=5 => Some(Kind::Wipe(direction)),
+            6..=9 => Some(Kind::Slide(direction)),
+            10..=13 => Some(Kind::Push(direction)),
+            14 => Some(Kind::Dissolve),
+            15 => Some(Kind::Iris),
+            _ => None,
+        };
+    }
+}
+
+/// Names of the kinds in text, such as `wipe-left`
+pub const NAMES: [(&str, Kind); 16] = [
+    ("cut", Kind::Cut),
+    ("crossfade", Kind::Crossfade),
+    ("wipe-left", Kind::Wipe(Direction::Left)),
+    ("wipe-right", Kind::Wipe(Direction::Right)),
+    ("wipe-up", Kind::Wipe(Direction::Up)),
+    ("wipe-down", Kind::Wipe(Direction::Down)),
+    ("slide-left", Kind::Slide(Direction::Left)),
+    ("slide-right", Kind::Slide(Direction::Right)),
+    ("slide-up", Kind::Slide(Direction::Up)),
+    ("slide-down", Kind::Slide(Direction::Down)),
+    ("push-left", Kind::Push(Direction::Left)),
+    ("push-right", Kind::Push(Direction::Right)),
+    ("push-up", Kind::Push(Direction::Up)),
+    ("push-down", Kind::Push(Direction::Down)),
+    ("dissolve", Kind::Dissolve),
+    ("iris", Kind::Iris),
+];
+
+/// Scattered value from 0 to 254 for every pixel, the pixels of any 16x16
+/// block having different ones
+fn threshold(x: usize, y: usize) -> u8 {
+    // Bit reversal of the interleaved coordinates, as in an ordered dither
+    let (x, y) = (x as u32 & 15, y as u32 & 15);
+    let mut value = 0;
+    for bit in 0..4 {
+        value |= ((x >> bit) & 1) << (7 - 2 * bit);
+        value |= (((x ^ y) >> bit) & 1) << (6 - 2 * bit);
+    }
+    return (value * 255 / 256) as u8;
+}
+
+/// A kind of transition and how long it lasts
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Transition {
+    pub kind: Kind,
+    pub duration_ms: u32,
+}
+
+impl Transition {
+    pub const CUT: Transition = Transition {
+        kind: Kind::Cut,
+        duration_ms: 0,
+    };
+
+    /// Unit of the duration on the serial line
+    pub const HEADER_UNIT_MS: u32 = 10;
+
+    pub const fn new(kind: Kind, duration_ms: u32) -> Self {
+        return Transition { kind, duration_ms };
+    }
+
+    /// Kind and duration in units of `HEADER_UNIT_MS`, as sent before an
+    /// image on the serial line. Both bytes stay below the start byte, the
+    /// duration being capped to 2.54 s.
+    pub fn to_header(&self) -> [u8; 2] {
+        let duration = self.duration_ms / Transition::HEADER_UNIT_MS;
+        return [self.kind.to_byte(), duration.min(254) as u8];
+    }
+
+    pub fn from_header(header: [u8; 2]) -> Option<Transition> {
+        let kind = Kind::from_byte(header[0])?;
+        return Some(Transition::new(
+            kind,
+            header[1] as u32 * Transition::HEADER_UNIT_MS,
+        ));
+    }
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        return Transition::CUT;
+    }
+}
+
+impl FromStr for Transition {
+    type Err = Error;
+
+    /// Parse a kind name from `NAMES` followed by the duration in
+    /// milliseconds, such as `crossfade:500`. Without a duration the
+    /// transition is instantaneous.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, duration) = match s.split_once(':') {
+            Some((name, duration)) => (name, Some(duration)),
+            None => (s, None),
+        };
+        let (_, kind) = NAMES
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .ok_or(Error::UnknownKind)?;
+        let duration_ms = match duration {
+            Some(duration) => duration.parse().map_err(|_| Error::BadDuration)?,
+            None => 0,
+        };
+        return Ok(Transition::new(*kind, duration_ms));
+    }
+}
+
+impl fmt::Display for Transition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, _) = NAMES.iter().find(|(_, k)| *k == self.kind).unwrap();
+        write!(f, "{name}")?;
+        if self.duration_ms != 0 {
+            write!(f, ":{}", self.duration_ms)?;
+        }
+        return Ok(());
+    }
+}
+
+/// Transition under way on the panel. It keeps a copy of the image which was
+/// shown and the image mixing it with the new one, so that the image buffers
+/// shared with the other tasks are released as usual.
+pub struct Transitioner {
+    transition: Transition,
+    from: PanelImage,
+    image: PanelImage,
+    elapsed_us: u32,
+    running: bool,
+    /// How the progress goes along with the time, the same for every kind
+    pub easing: Easing,
+}
+
+impl Transitioner {
+    pub const fn new() -> Self {
+        return Transitioner {
+            transition: Transition::CUT,
+            from: PanelImage::new_solid(Color::BLACK),
+            image: PanelImage::new_solid(Color::BLACK),
+            elapsed_us: 0,
+            running: false,
+            easing: Easing::Linear,
+        };
+    }
+
+    /// Go from `from`, the image shown so far, to the next one with
+    /// `transition`, abandoning any transition under way. An instantaneous
+    /// transition only changes the target of the one under way, so that the
+    /// frames of a screensaver or of an effect do not cut it short.
+    pub fn start(&mut self, transition: Transition, from: &PanelImage) {
+        let instantaneous = transition.kind == Kind::Cut || transition.duration_ms == 0;
+        if instantaneous && self.running {
+            return;
+        }
+        self.from = match self.image() {
+            // Carry on from what is actually shown
+            Some(image) => image.clone(),
+            None => from.clone(),
+        };
+        self.transition = transition;
+        self.elapsed_us = 0;
+        self.running = !instantaneous;
+    }
+
+    /// Move on by `elapsed_us` microseconds towards `to`, the new image
+    pub fn advance(&mut self, elapsed_us: u32, to: &PanelImage) {
+        if !self.running {
+            return;
+        }
+        let duration_us = self.transition.duration_ms.saturating_mul(1000);
+        self.elapsed_us = self.elapsed_us.saturating_add(elapsed_us);
+        if self.elapsed_us >= duration_us {
+            self.running = false;
+            return;
+        }
+        let t = self.elapsed_us as f32 / duration_us as f32;
+        let progress = (self.easing.apply(t) * 255.0) as u8;
+        let kind = self.transition.kind;
+        kind.blend(&self.from, to, progress, &mut self.image);
+    }
+
+    /// What to show instead of the new image while the transition is under way
+    pub fn image(&self) -> Option<&PanelImage> {
+        return self.running.then_some(&self.image);
+    }
+}
+
+impl Default for Transitioner {
+    fn default() -> Self {
+        return Transitioner::new();
+    }
+}
@@ -0,0 +1,138 @@
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The data is not a RIFF WAVE file
+    NotWav,
+    /// A chunk is cut or the `fmt ` or `data` chunk is missing
+    UnexpectedEnd,
+    /// The samples are neither integer PCM of 8 to 32 bits nor 32 bits float
+    Unsupported { format: u16, bits: u16 },
+}
+
+/// Sample encodings found in the `fmt ` chunk
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Unsigned 8 bits or signed 16, 24 or 32 bits little-endian integers
+    Pcm,
+    /// 32 bits little-endian IEEE floats
+    Float,
+}
+
+/// A WAVE file borrowed from memory
+pub struct Wav<'a> {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub bits: u16,
+    pub encoding: Encoding,
+    data: &'a [u8],
+}
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_FLOAT: u16 = 3;
+const FORMAT_EXTENSIBLE: u16 = 0xfffe;
+
+impl<'a> Wav<'a> {
+    pub fn parse(file: &'a [u8]) -> Result<Self, Error> {
+        if file.len() < 12 || &file[..4] != b"RIFF" || &file[8..12] != b"WAVE" {
+            return Err(Error::NotWav);
+        }
+        let mut format = None;
+        let mut pos = 12;
+        while pos + 8 <= file.len() {
+            let id = &file[pos..pos + 4];
+            let len =
+                u32::from_le_bytes([file[pos + 4], file[pos + 5], file[pos + 6], file[pos + 7]])
+                    as usize;
+            let body = &file[pos + 8..];
+            match id {
+                b"fmt " => {
+                    if len < 16 || body.len() < len {
+                        return Err(Error::UnexpectedEnd);
+                    }
+                    format = Some(body);
+                }
+                b"data" => {
+                    let (mut tag, channels, sample_rate, bits) = {
+                        let fmt = format.ok_or(Error::UnexpectedEnd)?;
+                        let field = |i: usize| u16::from_le_bytes([fmt[i], fmt[i + 1]]);
+                        (
+                            field(0),
+                            field(2),
+                            u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]),
+                            field(14),
+                        )
+                    };
+                    if tag == FORMAT_EXTENSIBLE {
+                        // The actual format is the start of the sub-format GUID
+                        let fmt = format.unwrap();
+                        if fmt.len() < 26 {
+                            return Err(Error::UnexpectedEnd);
+                        }
+                        tag = u16::from_le_bytes([fmt[24], fmt[25]]);
+                    }
+                    let encoding = match (tag, bits) {
+                        (FORMAT_PCM, 8 | 16 | 24 | 32) => Encoding::Pcm,
+                        (FORMAT_FLOAT, 32) => Encoding::Float,
+                        _ => return Err(Error::Unsupported { format: tag, bits }),
+                    };
+                    if channels == 0 || sample_rate == 0 {
+                        return Err(Error::Unsupported { format: tag, bits });
+                    }
+                    // Files being recorded often have a wrong data length, so
+                    // only take what is actually there
+                    let data = &body[..len.min(body.len())];
+                    return Ok(Wav {
+                        sample_rate,
+                        channels,
+                        bits,
+                        encoding,
+                        data,
+                    });
+                }
+                _ => {}
+            }
+            // Chunks are padded to an even length
+            pos += 8 + len + (len & 1);
+        }
+        return Err(Error::UnexpectedEnd);
+    }
+
+    fn frame_len(&self) -> usize {
+        return self.channels as usize * (self.bits as usize / 8);
+    }
+
+    /// Number of samples per channel
+    pub fn len(&self) -> usize {
+        return self.data.len() / self.frame_len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Samples in the -1.0..1.0 range, the channels being mixed together
+    pub fn mono(&self) -> impl Iterator<Item = f32> + 'a {
+        let (bits, encoding, channels) = (self.bits, self.encoding, self.channels as usize);
+        return self.data.chunks_exact(self.frame_len()).map(move |frame| {
+            let sum: f32 = frame
+                .chunks_exact(bits as usize / 8)
+                .map(|sample| decode_sample(sample, encoding))
+                .sum();
+            sum / channels as f32
+        });
+    }
+}
+
+/// Decode one little-endian sample to the -1.0..1.0 range
+pub fn decode_sample(sample: &[u8], encoding: Encoding) -> f32 {
+    return match (encoding, sample.len()) {
+        (Encoding::Float, _) => f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]),
+        (Encoding::Pcm, 1) => (sample[0] as f32 - 128.0) / 128.0,
+        (Encoding::Pcm, 2) => i16::from_le_bytes([sample[0], sample[1]]) as f32 / 32768.0,
+        (Encoding::Pcm, 3) => {
+            i32::from_le_bytes([0, sample[0], sample[1], sample[2]]) as f32 / 2147483648.0
+        }
+        (Encoding::Pcm, _) => {
+            i32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]) as f32 / 2147483648.0
+        }
+    };
+}